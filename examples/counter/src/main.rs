@@ -6,9 +6,14 @@ fn main() {
     let counter = SignalMut::new(0);
     let text = counter.map(|n| format!("Counter value: {n}"));
 
+    let title = html::h1(());
+    title.with(text);
+    let button = html::button(());
+    button.with("Increment Counter");
+
     html::div(())
-        .with(html::h1(()).text(text))
-        .with(html::button(()).text("Increment Counter"))
+        .with(title)
+        .with(button)
         .attach_to_body()
         .unwrap_or_else(|_| log::error!("root element not found"));
 }
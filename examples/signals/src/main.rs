@@ -0,0 +1,27 @@
+use wasmadeus::prelude::*;
+
+fn main() {
+    ConsoleLogger::new().init().unwrap();
+
+    let count = SignalMut::new(0u32);
+    let label = count.map(|n| format!("{n}"));
+    let even = count.map(|n| n % 2 == 0);
+    let ratio = count.map(|&n| f64::from(n) / 10.0);
+    let digits = label.map(|label| label.chars().filter_map(|c| c.to_digit(10)).collect::<Vec<_>>());
+    let bytes = count.map(|&n| (n as u8, n as u16));
+    let parity = even.map(|&even| if even { Some("even") } else { None });
+    let last = digits.map(|digits| digits.last().copied());
+
+    count.for_each_forever(|n| log::info!("count: {n}"));
+    label.for_each_forever(|label| log::info!("label: {label}"));
+    even.for_each_forever(|even| log::info!("even: {even}"));
+    ratio.for_each_forever(|ratio| log::info!("ratio: {ratio}"));
+    digits.for_each_forever(|digits| log::info!("digits: {digits:?}"));
+    bytes.for_each_forever(|bytes| log::info!("bytes: {bytes:?}"));
+    parity.for_each_forever(|parity| log::info!("parity: {parity:?}"));
+    last.for_each_forever(|last| log::info!("last digit: {last:?}"));
+
+    for n in 1..=12 {
+        count.set(n);
+    }
+}
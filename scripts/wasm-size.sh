@@ -0,0 +1,62 @@
+#!/usr/bin/env sh
+
+# Compares the size of the examples compiled to wasm, between a base revision and another revision
+# (the working tree by default). The examples of the working tree are built against both revisions,
+# so that the comparison only measures the crate. Needs the wasm32-unknown-unknown target, and uses
+# wasm-opt if installed.
+#
+# usage: scripts/wasm-size.sh base-revision [revision]
+
+set -e
+
+if [ -z "$1" ]; then
+    echo "usage: $0 base-revision [revision]" >&2
+    exit 1
+fi
+
+BASE=$1
+REVISION=$2
+ROOT=`git rev-parse --show-toplevel`
+TMP=`mktemp -d`
+trap 'for TREE in "$TMP"/tree-*; do git -C "$ROOT" worktree remove --force "$TREE" > /dev/null 2>&1; done; rm -rf "$TMP"' EXIT
+
+# Checks out the revision $1 in a new worktree, with the examples of the working tree, and prints its path.
+checkout() {
+    TREE="$TMP/tree-$2"
+    git -C "$ROOT" worktree add --detach --quiet "$TREE" "$1"
+    rm -rf "$TREE/examples"
+    (cd "$ROOT" && tar cf - --exclude target examples) | (cd "$TREE" && tar xf -)
+    echo "$TREE"
+}
+
+# Builds the examples of the repository at $1, and copies the wasm files to $2.
+build() {
+    mkdir -p "$2"
+    (cd "$1/examples" && CARGO_TARGET_DIR="$TMP/target-`basename $2`" cargo build --release --quiet --target wasm32-unknown-unknown)
+    for WASM in "$TMP"/target-`basename $2`/wasm32-unknown-unknown/release/*.wasm; do
+        if command -v wasm-opt > /dev/null; then
+            wasm-opt -Oz "$WASM" -o "$2/`basename $WASM`"
+        else
+            cp "$WASM" "$2"
+        fi
+    done
+}
+
+echo building $BASE
+build `checkout "$BASE" base` "$TMP/before"
+
+if [ -n "$REVISION" ]; then
+    echo building $REVISION
+    build `checkout "$REVISION" revision` "$TMP/after"
+else
+    echo building working tree
+    build "$ROOT" "$TMP/after"
+fi
+
+printf '\n%-20s %10s %10s %10s\n' example before after diff
+for WASM in "$TMP"/after/*.wasm; do
+    NAME=`basename $WASM .wasm`
+    AFTER=`wc -c < "$WASM"`
+    BEFORE=`wc -c < "$TMP/before/$NAME.wasm" 2> /dev/null || echo 0`
+    printf '%-20s %10d %10d %+10d\n' $NAME $BEFORE $AFTER `expr $AFTER - $BEFORE`
+done
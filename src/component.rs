@@ -10,7 +10,7 @@ use web_sys::wasm_bindgen::JsCast;
 use web_sys::{CssStyleDeclaration, Element, HtmlElement, SvgElement};
 
 use crate::attribute::Attributes;
-use crate::signal::{Unsubscribe, Value};
+use crate::signal::{Scope, Unsubscribe, Value};
 use crate::view::View;
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
@@ -47,6 +47,7 @@ struct ComponentInner {
     element: ElementKind,
     style: Option<CssStyleDeclaration>,
     deps: UnsafeCell<Vec<Box<dyn Any>>>,
+    scope: Scope,
 }
 
 #[derive(Clone, Debug)]
@@ -86,10 +87,11 @@ impl Component {
             element,
             style,
             deps: Default::default(),
+            scope: Scope::new(),
         }));
 
-        // Apply attributes and return.
-        attributes.apply_to(&this);
+        // Apply attributes in the component's scope and return.
+        this.scope().run(|| attributes.apply_to(&this));
         this
     }
 
@@ -133,6 +135,13 @@ impl Component {
         self.inner().style.as_ref()
     }
 
+    /// The scope owning the subscriptions made while building this component, released when
+    /// the component is dropped.
+    #[inline]
+    pub fn scope(&self) -> &Scope {
+        &self.inner().scope
+    }
+
     #[inline]
     pub fn set_visible<T: Value<Item = bool>>(&self, visible: T) {
         if let Some(style) = self.style().cloned() {
@@ -198,8 +207,7 @@ impl Component {
 
     #[inline]
     pub fn with<V: View>(&self, view: V) -> &Self {
-        let state = view.init(self);
-        self.push_dependency(state);
+        self.scope().run(|| view.init(self));
         self
     }
 
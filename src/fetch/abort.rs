@@ -0,0 +1,94 @@
+use core::cell::{Cell, OnceCell, RefCell};
+use core::future::{self, Future};
+use core::task::{Poll, Waker};
+
+use alloc::rc::Rc;
+use web_sys::{AbortController, AbortSignal};
+
+use super::FetchError;
+
+#[derive(Default, Debug)]
+struct AbortState {
+    aborted: Cell<bool>,
+    timed_out: Cell<bool>,
+    /// Only created when the request is sent by the browser.
+    controller: OnceCell<AbortController>,
+    /// The task waiting for the request to be aborted, if any.
+    waker: RefCell<Option<Waker>>,
+}
+
+/// Aborts a request started with [`Fetch::execute_abortable`](super::Fetch::execute_abortable).
+///
+/// Requests sent by the browser are aborted with an [`AbortController`](https://developer.mozilla.org/en-US/docs/Web/API/AbortController).
+#[derive(Clone, Default, Debug)]
+pub struct AbortHandle(Rc<AbortState>);
+
+impl AbortHandle {
+    #[inline]
+    pub(super) fn new() -> Self {
+        Self::default()
+    }
+
+    /// The signal to pass to the browser, aborted along with this handle.
+    pub fn signal(&self) -> AbortSignal {
+        let controller = self.0.controller.get_or_init(|| {
+            let controller = AbortController::new().unwrap();
+            if self.is_aborted() {
+                controller.abort();
+            }
+            controller
+        });
+        controller.signal()
+    }
+
+    /// Aborts the request, which then fails with [`FetchError::Aborted`].
+    ///
+    /// Does nothing if the request is already complete.
+    #[inline]
+    pub fn abort(&self) {
+        self.0.aborted.set(true);
+        if let Some(controller) = self.0.controller.get() {
+            controller.abort();
+        }
+        if let Some(waker) = self.0.waker.take() {
+            waker.wake();
+        }
+    }
+
+    #[inline]
+    pub fn is_aborted(&self) -> bool {
+        self.0.aborted.get()
+    }
+
+    /// Returns a future that resolves once the request is aborted.
+    ///
+    /// Only the task that polled the future last is woken up.
+    pub(super) fn aborted(&self) -> impl Future<Output = ()> + '_ {
+        future::poll_fn(move |cx| {
+            if self.is_aborted() {
+                return Poll::Ready(());
+            }
+            self.0.waker.replace(Some(cx.waker().clone()));
+            Poll::Pending
+        })
+    }
+
+    /// Aborts the request because it timed out.
+    #[inline]
+    pub(super) fn time_out(&self) {
+        self.0.timed_out.set(true);
+        self.abort();
+    }
+
+    /// Returns the error the request failed with, given the error reported by its transport.
+    #[inline]
+    pub(super) fn error(&self, error: FetchError) -> FetchError {
+        if self.0.timed_out.get() {
+            FetchError::TimedOut
+        } else if self.is_aborted() {
+            FetchError::Aborted
+        } else {
+            error
+        }
+    }
+}
@@ -0,0 +1,232 @@
+use core::fmt;
+use core::future::Future;
+
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::signal::Clock;
+
+use super::{AbortHandle, Credentials, Fetch, FetchError, HeaderError, HeaderMap, Method, Mode, Response};
+use super::{FetchClock, ResponseBody, RetryPolicy, Transport, TransportFuture};
+
+type RequestInterceptor = Rc<dyn Fn(&mut Fetch)>;
+
+type ResponseInterceptor = Rc<dyn Fn(Fetch, Result<Response, FetchError>) -> TransportFuture>;
+
+/// Builds and executes [`Fetch`] requests sharing the same defaults, passing them through a chain
+/// of interceptors.
+///
+/// ```
+/// # use wasmadeus::fetch::*;
+/// let mut client = Client::new();
+/// client
+///     .with_base_url("https://example.com/api/")
+///     .with_credentials(Credentials::Include)
+///     .with_request_interceptor(|request| {
+///         request.with_bearer_auth("token").unwrap();
+///     })
+///     .with_response_interceptor(|_, result| async move {
+///         match result {
+///             Ok(response) if response.status() == 401 => Err(FetchError::Aborted),
+///             result => result,
+///         }
+///     });
+///
+/// let request = client.get("users/1");
+/// assert_eq!(request.url(), "https://example.com/api/users/1");
+/// ```
+#[derive(Clone, Default)]
+pub struct Client {
+    base_url: Option<String>,
+    headers: HeaderMap,
+    credentials: Option<Credentials>,
+    mode: Option<Mode>,
+    timeout: Option<u32>,
+    retry: Option<RetryPolicy>,
+    transport: Option<Rc<dyn Transport>>,
+    clock: Option<Rc<dyn FetchClock>>,
+    request_interceptors: Vec<RequestInterceptor>,
+    response_interceptors: Vec<ResponseInterceptor>,
+}
+
+impl Client {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a request with the defaults of this client.
+    pub fn request<U: ToString>(&self, method: Method, url: U) -> Fetch {
+        let mut request = Fetch::new(method, url);
+        request.base_url.clone_from(&self.base_url);
+        request.headers.clone_from(&self.headers);
+        request.credentials = self.credentials;
+        request.mode = self.mode;
+        request.timeout = self.timeout;
+        request.retry.clone_from(&self.retry);
+        request.transport.clone_from(&self.transport);
+        request.clock.clone_from(&self.clock);
+        request
+    }
+
+    #[inline]
+    pub fn get<U: ToString>(&self, url: U) -> Fetch {
+        self.request(Method::Get, url)
+    }
+
+    #[inline]
+    pub fn post<U: ToString>(&self, url: U) -> Fetch {
+        self.request(Method::Post, url)
+    }
+
+    #[inline]
+    pub fn head<U: ToString>(&self, url: U) -> Fetch {
+        self.request(Method::Head, url)
+    }
+
+    #[inline]
+    pub fn put<U: ToString>(&self, url: U) -> Fetch {
+        self.request(Method::Put, url)
+    }
+
+    #[inline]
+    pub fn delete<U: ToString>(&self, url: U) -> Fetch {
+        self.request(Method::Delete, url)
+    }
+
+    #[inline]
+    pub fn patch<U: ToString>(&self, url: U) -> Fetch {
+        self.request(Method::Patch, url)
+    }
+
+    /// See [`Fetch::with_base_url`].
+    #[inline]
+    pub fn with_base_url<S: ToString>(&mut self, base_url: S) -> &mut Self {
+        self.base_url = Some(base_url.to_string());
+        self
+    }
+
+    /// Sets a header sent with every request, see [`Fetch::with_header`].
+    #[inline]
+    pub fn with_header(&mut self, name: &str, value: &str) -> Result<&mut Self, HeaderError> {
+        self.headers.insert(name, value)?;
+        Ok(self)
+    }
+
+    #[inline]
+    pub fn with_credentials(&mut self, credentials: Credentials) -> &mut Self {
+        self.credentials = Some(credentials);
+        self
+    }
+
+    #[inline]
+    pub fn with_mode(&mut self, mode: Mode) -> &mut Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    /// See [`Fetch::with_timeout`].
+    #[inline]
+    pub fn with_timeout(&mut self, ms: u32) -> &mut Self {
+        self.timeout = Some(ms);
+        self
+    }
+
+    /// See [`Fetch::with_retry`].
+    #[inline]
+    pub fn with_retry(&mut self, retry: RetryPolicy) -> &mut Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// See [`Fetch::with_transport`].
+    #[inline]
+    pub fn with_transport<T: Transport>(&mut self, transport: T) -> &mut Self {
+        self.transport = Some(Rc::new(transport));
+        self
+    }
+
+    /// See [`Fetch::with_clock`].
+    #[inline]
+    pub fn with_clock<C: Clock>(&mut self, clock: C) -> &mut Self {
+        self.clock = Some(Rc::new(clock));
+        self
+    }
+
+    /// Adds an interceptor called on each request before it is sent, after the interceptors added before it.
+    #[inline]
+    pub fn with_request_interceptor<F>(&mut self, interceptor: F) -> &mut Self
+    where
+        F: Fn(&mut Fetch) + 'static,
+    {
+        self.request_interceptors.push(Rc::new(interceptor));
+        self
+    }
+
+    /// Adds an interceptor called with the request and the result of each request before its body is read,
+    /// after the interceptors added before it.
+    ///
+    /// The interceptor returns the result passed to the next one, it can for example execute the request
+    /// again after refreshing an expired token.
+    #[inline]
+    pub fn with_response_interceptor<F, Fut>(&mut self, interceptor: F) -> &mut Self
+    where
+        F: Fn(Fetch, Result<Response, FetchError>) -> Fut + 'static,
+        Fut: Future<Output = Result<Response, FetchError>> + 'static,
+    {
+        self.response_interceptors
+            .push(Rc::new(move |request, result| Box::pin(interceptor(request, result))));
+        self
+    }
+
+    /// Executes `request` through the interceptors of this client, and reads the body of the response as `R`.
+    #[inline]
+    pub fn execute<R: ResponseBody>(&self, request: &Fetch) -> impl Future<Output = Result<R, R::Error>> {
+        self.execute_abortable(request).1
+    }
+
+    /// Same as [`Client::execute`], also returning a handle to abort the request.
+    ///
+    /// The request interceptors are called right away, but the request is only sent when the future is
+    /// first polled.
+    pub fn execute_abortable<R: ResponseBody>(
+        &self,
+        request: &Fetch,
+    ) -> (AbortHandle, impl Future<Output = Result<R, R::Error>>) {
+        let mut request = request.clone();
+        for interceptor in &self.request_interceptors {
+            interceptor(&mut request);
+        }
+
+        let (handle, response) = request.execute_abortable::<Response>();
+        let interceptors = self.response_interceptors.clone();
+        let future = async move {
+            let mut result = response.await;
+            for interceptor in &interceptors {
+                result = interceptor(request.clone(), result).await;
+            }
+            R::from_response(&result?).await
+        };
+
+        (handle, future)
+    }
+}
+
+impl fmt::Debug for Client {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Client")
+            .field("base_url", &self.base_url)
+            .field("headers", &self.headers)
+            .field("credentials", &self.credentials)
+            .field("mode", &self.mode)
+            .field("timeout", &self.timeout)
+            .field("retry", &self.retry)
+            .field("transport", &self.transport)
+            .field("clock", &self.clock)
+            .field("request_interceptors", &self.request_interceptors.len())
+            .field("response_interceptors", &self.response_interceptors.len())
+            .finish()
+    }
+}
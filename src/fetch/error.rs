@@ -0,0 +1,38 @@
+use core::fmt;
+
+use alloc::string::String;
+use web_sys::wasm_bindgen::JsValue;
+
+/// An error that occured while executing a [`Fetch`](super::Fetch) request.
+#[non_exhaustive]
+#[derive(Debug)]
+pub enum FetchError {
+    /// The request failed because of a network error, or was refused by the CORS policy.
+    Network(JsValue),
+    /// The request was aborted with its [`AbortHandle`](super::AbortHandle).
+    Aborted,
+    /// The request did not complete before its [timeout](super::Fetch::with_timeout).
+    TimedOut,
+    /// The body of the response could not be read.
+    Body(JsValue),
+    /// The [`Transport`](super::Transport) of the request failed to send it.
+    Transport(String),
+    /// The body of the response is not valid JSON for the expected type.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    Json(serde_json::Error),
+}
+
+impl fmt::Display for FetchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Network(error) => write!(f, "network error: {error:?}"),
+            Self::Aborted => write!(f, "request was aborted"),
+            Self::TimedOut => write!(f, "request timed out"),
+            Self::Body(error) => write!(f, "could not read response body: {error:?}"),
+            Self::Transport(error) => write!(f, "transport error: {error}"),
+            #[cfg(feature = "json")]
+            Self::Json(error) => write!(f, "invalid json response: {error}"),
+        }
+    }
+}
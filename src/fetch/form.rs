@@ -0,0 +1,144 @@
+use core::convert::Infallible;
+
+use alloc::string::String;
+use web_sys::{Blob, File, FormData};
+
+use super::{url, Body, RequestBody};
+
+/// An `application/x-www-form-urlencoded` body, as sent by HTML forms by default.
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct FormUrlEncoded(String);
+
+impl FormUrlEncoded {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a form with the given pairs.
+    #[inline]
+    pub fn from_pairs<I, K, V>(pairs: I) -> Self
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        let mut form = Self::new();
+        for (key, value) in pairs {
+            form.append(key.as_ref(), value.as_ref());
+        }
+        form
+    }
+
+    /// Creates a form with the fields of `value`, which must serialize to a map.
+    ///
+    /// Fields holding `None` are skipped, and the values of fields holding sequences are each
+    /// appended with the name of the field.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn from_struct<T: serde::Serialize>(value: &T) -> Result<Self, serde_json::Error> {
+        Ok(Self::from_pairs(url::struct_pairs(value)?))
+    }
+
+    /// Appends the pair `key=value` to the form.
+    #[inline]
+    pub fn append(&mut self, key: &str, value: &str) -> &mut Self {
+        if !self.0.is_empty() {
+            self.0.push('&');
+        }
+        self.0.push_str(&url::encode_form_component(key));
+        self.0.push('=');
+        self.0.push_str(&url::encode_form_component(value));
+        self
+    }
+
+    /// The encoded form.
+    #[inline]
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl RequestBody for FormUrlEncoded {
+    type Error = Infallible;
+
+    #[inline]
+    fn content_type(&self) -> Option<&'static str> {
+        Some("application/x-www-form-urlencoded;charset=UTF-8")
+    }
+
+    #[inline]
+    fn to_body(&self) -> Result<Body, Self::Error> {
+        Ok(Body::Text(self.0.clone()))
+    }
+}
+
+/// A `multipart/form-data` body, holding text fields and files, built on [`FormData`].
+///
+/// The browser sets the `Content-Type` header itself, with the boundary between the parts.
+#[derive(Clone, Debug)]
+pub struct Multipart(FormData);
+
+impl Multipart {
+    #[inline]
+    pub fn new() -> Self {
+        Self(FormData::new().unwrap())
+    }
+
+    /// Appends a text field.
+    #[inline]
+    pub fn text(&mut self, name: &str, value: &str) -> &mut Self {
+        self.0.append_with_str(name, value).unwrap();
+        self
+    }
+
+    /// Appends a binary part, with an optional file name.
+    #[inline]
+    pub fn blob(&mut self, name: &str, blob: &Blob, filename: Option<&str>) -> &mut Self {
+        match filename {
+            Some(filename) => self.0.append_with_blob_and_filename(name, blob, filename),
+            _ => self.0.append_with_blob(name, blob),
+        }
+        .unwrap();
+        self
+    }
+
+    /// Appends a file, with its name.
+    #[inline]
+    pub fn file(&mut self, name: &str, file: &File) -> &mut Self {
+        self.blob(name, file, Some(&file.name()))
+    }
+
+    #[inline]
+    pub fn as_form_data(&self) -> &FormData {
+        &self.0
+    }
+}
+
+impl Default for Multipart {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl From<FormData> for Multipart {
+    #[inline]
+    fn from(form_data: FormData) -> Self {
+        Self(form_data)
+    }
+}
+
+impl RequestBody for Multipart {
+    type Error = Infallible;
+
+    #[inline]
+    fn content_type(&self) -> Option<&'static str> {
+        None
+    }
+
+    #[inline]
+    fn to_body(&self) -> Result<Body, Self::Error> {
+        Ok(Body::Js(self.0.clone().into()))
+    }
+}
@@ -0,0 +1,175 @@
+use core::fmt;
+
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use web_sys::js_sys::{self, Array};
+use web_sys::Headers;
+
+/// An error returned when adding an invalid header to a [`Fetch`](super::Fetch) request.
+#[non_exhaustive]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub enum HeaderError {
+    /// The name is empty or contains characters other than letters, digits and ``!#$%&'*+-.^_`|~``.
+    InvalidName(String),
+    /// The value contains control characters, such as line breaks, or characters outside of Latin-1
+    /// (which browsers reject).
+    InvalidValue(String),
+}
+
+impl fmt::Display for HeaderError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidName(name) => write!(f, "invalid header name: {name:?}"),
+            Self::InvalidValue(value) => write!(f, "invalid header value: {value:?}"),
+        }
+    }
+}
+
+/// Returns true iff `name` is a valid header name, as defined by [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-tokens).
+fn is_valid_name(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .bytes()
+            .all(|byte| byte.is_ascii_alphanumeric() || b"!#$%&'*+-.^_`|~".contains(&byte))
+}
+
+/// Returns true iff `value` is a valid header value, as defined by [RFC 9110](https://www.rfc-editor.org/rfc/rfc9110#name-field-values).
+///
+/// Browsers only accept values made of Latin-1 characters, which they send as single bytes.
+fn is_valid_value(value: &str) -> bool {
+    value
+        .chars()
+        .all(|char| char == '\t' || (char >= ' ' && char != '\x7F' && char <= '\u{FF}'))
+}
+
+/// A list of HTTP headers, whose names are case-insensitive.
+///
+/// Names are stored in lowercase, in insertion order.
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct HeaderMap(Vec<(String, String)>);
+
+impl HeaderMap {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Copies the headers of a browser [`Headers`] object.
+    pub fn from_headers(headers: &Headers) -> Self {
+        let mut map = Self::new();
+        if let Ok(Some(entries)) = js_sys::try_iter(headers) {
+            for entry in entries.flatten() {
+                let entry = Array::from(&entry);
+                if let (Some(name), Some(value)) = (entry.get(0).as_string(), entry.get(1).as_string()) {
+                    map.0.push((name.to_ascii_lowercase(), value));
+                }
+            }
+        }
+        map
+    }
+
+    /// Returns the value of the first header named `name`, if any.
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.0
+            .iter()
+            .find(|(other, _)| other.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the values of all the headers named `name`.
+    #[inline]
+    pub fn get_all<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a str> + 'a {
+        self.0
+            .iter()
+            .filter(move |(other, _)| other.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    #[inline]
+    pub fn contains(&self, name: &str) -> bool {
+        self.get(name).is_some()
+    }
+
+    /// Sets the header `name` to `value`, replacing all the previous headers with that name.
+    pub fn insert(&mut self, name: &str, value: &str) -> Result<(), HeaderError> {
+        self.remove(name);
+        self.append(name, value)
+    }
+
+    /// Adds a header, keeping the previous headers with the same name.
+    pub fn append(&mut self, name: &str, value: &str) -> Result<(), HeaderError> {
+        if !is_valid_name(name) {
+            return Err(HeaderError::InvalidName(name.to_string()));
+        }
+        if !is_valid_value(value) {
+            return Err(HeaderError::InvalidValue(value.to_string()));
+        }
+        self.0.push((name.to_ascii_lowercase(), value.trim().to_string()));
+        Ok(())
+    }
+
+    /// Removes all the headers named `name`.
+    #[inline]
+    pub fn remove(&mut self, name: &str) {
+        self.0.retain(|(other, _)| !other.eq_ignore_ascii_case(name));
+    }
+
+    /// Iterates over the names and values of the headers, in insertion order.
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.0.iter().map(|(name, value)| (name.as_str(), value.as_str()))
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The value of the `Content-Type` header, if any.
+    #[inline]
+    pub fn content_type(&self) -> Option<&str> {
+        self.get("content-type")
+    }
+
+    /// The value of the `Content-Length` header, if any and valid.
+    #[inline]
+    pub fn content_length(&self) -> Option<u64> {
+        self.get("content-length")?.parse().ok()
+    }
+
+    /// Builds a browser [`Headers`] object.
+    pub(super) fn to_headers(&self) -> Headers {
+        let headers = Headers::new().unwrap();
+        for (name, value) in self.iter() {
+            // Names and values were validated.
+            headers.append(name, value).unwrap();
+        }
+        headers
+    }
+}
+
+/// Encodes `bytes` in [base64](https://www.rfc-editor.org/rfc/rfc4648#section-4), with padding.
+pub(super) fn base64(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+    let mut encoded = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let triple = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |acc, (i, &byte)| acc | u32::from(byte) << (16 - 8 * i));
+        for i in 0..4 {
+            match i <= chunk.len() {
+                true => encoded.push(ALPHABET[(triple >> (18 - 6 * i) & 0x3F) as usize] as char),
+                false => encoded.push('='),
+            }
+        }
+    }
+    encoded
+}
@@ -0,0 +1,127 @@
+use core::cell::RefCell;
+use core::future;
+
+use alloc::boxed::Box;
+use alloc::format;
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use super::{AbortHandle, Body, Fetch, FetchError, HeaderError, HeaderMap, Method, RequestBody, Response};
+use super::{Transport, TransportFuture};
+
+/// The requests a mock of a [`MockTransport`] responds to.
+///
+/// Requests match if they have the same method and [url](Fetch::url), at least the expected headers,
+/// and the expected body if one was given.
+#[derive(Clone, Debug)]
+pub struct MockRequest {
+    method: Method,
+    url: String,
+    headers: HeaderMap,
+    body: Option<Body>,
+}
+
+impl MockRequest {
+    #[inline]
+    pub fn new<U: ToString>(method: Method, url: U) -> Self {
+        Self {
+            method,
+            url: url.to_string(),
+            headers: HeaderMap::new(),
+            body: None,
+        }
+    }
+
+    /// Only matches requests with the header `name` set to `value`.
+    #[inline]
+    pub fn with_header(mut self, name: &str, value: &str) -> Result<Self, HeaderError> {
+        self.headers.append(name, value)?;
+        Ok(self)
+    }
+
+    /// Only matches requests with the given body.
+    #[inline]
+    pub fn with_body<B: RequestBody>(mut self, body: &B) -> Result<Self, B::Error> {
+        self.body = Some(body.to_body()?);
+        Ok(self)
+    }
+
+    /// Returns true iff `request` matches this mock request.
+    pub fn matches(&self, request: &Fetch) -> bool {
+        self.method == request.method()
+            && self.url == request.url()
+            && self
+                .headers
+                .iter()
+                .all(|(name, value)| request.headers().get_all(name).any(|other| other == value))
+            && self.body.as_ref().is_none_or(|body| request.body() == Some(body))
+    }
+}
+
+#[derive(Default, Debug)]
+struct MockTransportInner {
+    mocks: Vec<(MockRequest, Response)>,
+    requests: Vec<(Method, String)>,
+}
+
+/// A [`Transport`] responding to requests with canned responses, so that code using [`Fetch`] can be
+/// tested without a browser.
+///
+/// Clones of a mock transport share the same mocks.
+///
+/// ```
+/// # use wasmadeus::fetch::*;
+/// let transport = MockTransport::new();
+/// transport.mock(
+///     MockRequest::new(Method::Get, "/users/1"),
+///     Response::new(200, HeaderMap::new(), r#"{"name":"Jane"}"#),
+/// );
+///
+/// let mut fetch = Fetch::get("/users/1");
+/// fetch.with_transport(transport.clone());
+/// ```
+#[derive(Clone, Default, Debug)]
+pub struct MockTransport(Rc<RefCell<MockTransportInner>>);
+
+impl MockTransport {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Responds with `response` to the requests matching `request`.
+    ///
+    /// Mocks are tried in the order they were added, the first matching one responds.
+    #[inline]
+    pub fn mock(&self, request: MockRequest, response: Response) -> &Self {
+        self.0.borrow_mut().mocks.push((request, response));
+        self
+    }
+
+    /// The method and url of the requests sent so far, in order.
+    #[inline]
+    pub fn requests(&self) -> Vec<(Method, String)> {
+        self.0.borrow().requests.clone()
+    }
+}
+
+impl Transport for MockTransport {
+    fn send(&self, request: &Fetch, abort: &AbortHandle) -> TransportFuture {
+        if abort.is_aborted() {
+            return Box::pin(future::ready(Err(FetchError::Aborted)));
+        }
+
+        let mut inner = self.0.borrow_mut();
+        inner.requests.push((request.method(), request.url()));
+        let result = match inner.mocks.iter().find(|(mock, _)| mock.matches(request)) {
+            Some((_, response)) => Ok(response.clone()),
+            _ => Err(FetchError::Transport(format!(
+                "no mock matches {} {}",
+                request.method().as_str(),
+                request.url()
+            ))),
+        };
+        Box::pin(future::ready(result))
+    }
+}
@@ -0,0 +1,832 @@
+// Links to resources about the Fetch API, and how to use it with wasm-bindgen:
+// * https://developer.mozilla.org/en-US/docs/Web/API/Fetch_API
+// * https://rustwasm.github.io/wasm-bindgen/examples/fetch.html
+// * https://docs.rs/web-sys/latest/web_sys/struct.Request.html#
+// * https://docs.rs/web-sys/latest/web_sys/struct.RequestInit.html#
+
+mod abort;
+mod client;
+mod error;
+mod form;
+mod headers;
+mod mock;
+mod query;
+mod response;
+mod retry;
+mod stream;
+mod transport;
+mod url;
+
+use core::cell::RefCell;
+use core::convert::Infallible;
+use core::fmt;
+use core::future::Future;
+use core::pin::{pin, Pin};
+use core::task::{Poll, Waker};
+
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use futures::future::{self, Either};
+use web_sys::js_sys::Uint8Array;
+use web_sys::wasm_bindgen::JsValue;
+use web_sys::{RequestCache, RequestCredentials, RequestInit, RequestMode, RequestRedirect};
+
+use crate::signal::{BrowserClock, Clock};
+
+pub use abort::*;
+pub use client::*;
+pub use error::*;
+pub use form::*;
+pub use headers::{HeaderError, HeaderMap};
+pub use mock::*;
+pub use query::*;
+pub use response::Response;
+pub use retry::*;
+pub use stream::*;
+pub use transport::*;
+pub use url::encode_uri_component;
+
+/// The body of a [`Fetch`] request, as given to its [`Transport`].
+#[derive(Clone, PartialEq, Debug)]
+pub enum Body {
+    Text(String),
+    Bytes(Vec<u8>),
+    /// Any value the browser accepts as a body, such as a `FormData` or a `Blob`.
+    Js(JsValue),
+}
+
+impl Body {
+    /// Converts the body to the value passed to the browser.
+    pub fn to_js(&self) -> JsValue {
+        match self {
+            Self::Text(text) => JsValue::from_str(text),
+            Self::Bytes(bytes) => Uint8Array::from(bytes.as_slice()).buffer().into(),
+            Self::Js(value) => value.clone(),
+        }
+    }
+}
+
+pub trait RequestBody {
+    type Error;
+
+    /// The value of the `Content-Type` header for this body, if it must be set.
+    ///
+    /// Some bodies, such as [`Multipart`], must let the browser set this header.
+    fn content_type(&self) -> Option<&'static str>;
+
+    fn to_body(&self) -> Result<Body, Self::Error>;
+}
+
+#[derive(Clone, Debug)]
+pub struct GenericBody {
+    pub content_type: Option<&'static str>,
+    pub value: JsValue,
+}
+
+impl GenericBody {
+    #[inline]
+    pub fn new(content_type: Option<&'static str>, value: JsValue) -> Self {
+        Self { content_type, value }
+    }
+}
+
+impl RequestBody for GenericBody {
+    type Error = Infallible;
+
+    #[inline]
+    fn content_type(&self) -> Option<&'static str> {
+        self.content_type
+    }
+
+    #[inline]
+    fn to_body(&self) -> Result<Body, Self::Error> {
+        Ok(Body::Js(self.value.clone()))
+    }
+}
+
+#[repr(transparent)]
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct Text(pub String);
+
+impl RequestBody for Text {
+    type Error = Infallible;
+
+    #[inline]
+    fn content_type(&self) -> Option<&'static str> {
+        Some("text/plain")
+    }
+
+    #[inline]
+    fn to_body(&self) -> Result<Body, Self::Error> {
+        Ok(Body::Text(self.0.clone()))
+    }
+}
+
+#[cfg(feature = "json")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fetch")))]
+#[repr(transparent)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct Json<T>(pub T);
+
+#[cfg(feature = "json")]
+impl<T: serde::Serialize> RequestBody for Json<T> {
+    type Error = serde_json::Error;
+
+    #[inline]
+    fn content_type(&self) -> Option<&'static str> {
+        Some("application/json")
+    }
+
+    #[inline]
+    fn to_body(&self) -> Result<Body, Self::Error> {
+        serde_json::to_string(&self.0).map(Body::Text)
+    }
+}
+
+#[cfg(feature = "bin")]
+#[cfg_attr(docsrs, doc(cfg(feature = "fetch")))]
+#[repr(transparent)]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct Bin<T>(pub T);
+
+#[cfg(feature = "bin")]
+impl<T: AsRef<[u8]>> RequestBody for Bin<T> {
+    type Error = Infallible;
+
+    #[inline]
+    fn content_type(&self) -> Option<&'static str> {
+        Some("application/octet-stream")
+    }
+
+    #[inline]
+    fn to_body(&self) -> Result<Body, Self::Error> {
+        Ok(Body::Bytes(self.0.as_ref().to_vec()))
+    }
+}
+
+pub trait ResponseBody: Sized {
+    type Error: From<FetchError>;
+
+    fn from_response(response: &Response) -> impl Future<Output = Result<Self, Self::Error>>;
+}
+
+impl ResponseBody for Response {
+    type Error = FetchError;
+
+    #[inline]
+    async fn from_response(response: &Response) -> Result<Self, Self::Error> {
+        Ok(response.clone())
+    }
+}
+
+impl ResponseBody for Text {
+    type Error = FetchError;
+
+    #[inline]
+    async fn from_response(response: &Response) -> Result<Self, Self::Error> {
+        response.text().await.map(Self)
+    }
+}
+
+#[cfg(feature = "json")]
+impl<T: serde::de::DeserializeOwned> ResponseBody for Json<T> {
+    type Error = FetchError;
+
+    #[inline]
+    async fn from_response(response: &Response) -> Result<Self, Self::Error> {
+        let text = response.text().await?;
+        serde_json::from_str(&text).map(Self).map_err(FetchError::Json)
+    }
+}
+
+#[cfg(feature = "bin")]
+impl ResponseBody for Bin<alloc::vec::Vec<u8>> {
+    type Error = FetchError;
+
+    #[inline]
+    async fn from_response(response: &Response) -> Result<Self, Self::Error> {
+        response.bytes().await.map(Self)
+    }
+}
+
+/// The [`Cache`] setting of a [`Fetch`] request controls how the request will interact with the browser's HTTP cache.
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub enum Cache {
+    /// The browser looks for a matching request in its HTTP cache:
+    /// * if there is a match and it is fresh, it will be returned from the cache.
+    /// * if there is a match but it is stale, the browser will make a conditional request to the remote server. If the server indicates that the resource has not changed, it will be returned from the cache. Otherwise the resource will be downloaded from the server and the cache will be updated.
+    /// * if there is no match, the browser will make a normal request, and will update the cache with the downloaded resource.
+    #[default]
+    Default,
+    /// The browser fetches the resource from the remote server without first looking in the cache, and will not update the cache with the downloaded resource.
+    NoStore,
+    /// The browser fetches the resource from the remote server without first looking in the cache, but then will update the cache with the downloaded resource.
+    Reload,
+    /// The browser looks for a matching request in its HTTP cache:
+    /// * if there is a match, fresh or stale, the browser will make a conditional request to the remote server. If the server indicates that the resource has not changed, it will be returned from the cache. Otherwise the resource will be downloaded from the server and the cache will be updated.
+    /// * if there is no match, the browser will make a normal request, and will update the cache with the downloaded resource.
+    NoCache,
+    /// The browser looks for a matching request in its HTTP cache:
+    /// * if there is a match, fresh or stale, it will be returned from the cache.
+    /// * if there is no match, the browser will make a normal request, and will update the cache with the downloaded resource.
+    ForceCache,
+    /// The browser looks for a matching request in its HTTP cache:
+    /// * if there is a match, fresh or stale, it will be returned from the cache.
+    /// * if there is no match, the browser will respond with a 504 Gateway timeout status.
+    ///
+    /// The [`Cache::OnlyIfCached`] mode can only be used if the request's mode is [`Mode::SameOrigin`]. Cached redirects will be followed if the request's redirect property is "follow" and the redirects do not violate the "same-origin" mode.
+    OnlyIfCached,
+}
+
+/// The [`Credentials`] setting of a [`Fetch`] request indicates whether the user agent should send or receive cookies from the other domain in the case of cross-origin requests.
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub enum Credentials {
+    /// Never send or receive cookies.
+    Omit,
+    /// Send user credentials (cookies, basic http auth, etc..) if the URL is on the same origin as the calling script. This is the default value.
+    #[default]
+    SameOrigin,
+    /// Always send user credentials (cookies, basic http auth, etc..), even for cross-origin calls.
+    Include,
+}
+
+/// The [`Method`] setting of a [`Fetch`] request defines the HTTP verb to use.
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub enum Method {
+    /// The `GET` method requests a representation of the specified resource. Requests using `GET` should only retrieve data.
+    #[default]
+    Get,
+    /// The `HEAD` method asks for a response identical to a `GET` request, but without the response body.
+    Head,
+    /// The `POST` method submits an entity to the specified resource, often causing a change in state or side effects on the server.
+    Post,
+    /// The `PUT` method replaces all current representations of the target resource with the request payload.
+    Put,
+    /// The `DELETE` method deletes the specified resource.
+    Delete,
+    /// The `CONNECT` method establishes a tunnel to the server identified by the target resource.
+    Connect,
+    /// The `OPTIONS` method describes the communication options for the target resource.
+    Options,
+    /// The `TRACE` method performs a message loop-back test along the path to the target resource.
+    Trace,
+    /// The `PATCH` method applies partial modifications to a resource.
+    Patch,
+    /// Some other, as of yet unknown HTTP method.
+    Other(&'static str),
+}
+
+impl Method {
+    /// Returns the name of the method, as sent in requests.
+    #[inline]
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Get => "GET",
+            Self::Head => "HEAD",
+            Self::Post => "POST",
+            Self::Put => "PUT",
+            Self::Delete => "DELETE",
+            Self::Connect => "CONNECT",
+            Self::Options => "OPTIONS",
+            Self::Trace => "TRACE",
+            Self::Patch => "PATCH",
+            Self::Other(other) => other,
+        }
+    }
+}
+
+/// The [`Mode`] setting of a [`Fetch`] request is used to determine if cross-origin requests lead to valid responses, and which properties of the response are readable.
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub enum Mode {
+    #[default]
+    /// If a request is made to another origin with this mode set, the result is an error. You could use this to ensure that a request is always being made to your origin.
+    SameOrigin,
+    /// Prevents the method from being anything other than HEAD, GET or POST, and the headers from being anything other than CORS-safelisted request headers. If any ServiceWorkers intercept these requests, they may not add or override any headers except for those that are CORS-safelisted request headers. In addition, JavaScript may not access any properties of the resulting Response. This ensures that ServiceWorkers do not affect the semantics of the Web and prevents security and privacy issues arising from leaking data across domains.
+    NoCors,
+    /// Allows cross-origin requests, for example to access various APIs offered by 3rd party vendors. These are expected to adhere to the CORS protocol. Only a limited set of headers are exposed in the Response, but the body is readable.
+    Cors,
+    /// A mode for supporting navigation. The navigate value is intended to be used only by HTML navigation. A navigate request is created only while navigating between documents.
+    Navigate,
+}
+
+/// The [`Redirect`] setting of a [`Fetch`] request contains the mode for how redirects are handled.
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub enum Redirect {
+    /// Automatically follow redirects. Unless otherwise stated the redirect mode is set to follow.
+    #[default]
+    Follow,
+    /// Abort with an error if a redirect occurs.
+    Error,
+    /// Caller intends to process the response in another context. See WHATWG fetch standard for more information.
+    Manual,
+}
+
+/// The [`ReferrerPolicy`] setting of a [`Fetch`] request controls how much referrer information (sent with the `Referer` header) should be included with requests. Aside from the HTTP header, you can set this policy in HTML.
+#[non_exhaustive]
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub enum ReferrerPolicy {
+    /// The `Referer` header will be omitted: sent requests do not include any referrer information.
+    NoReferrer,
+    /// Send the `origin`, `path`, and `querystring` in `Referer` when the protocol security level stays the same or improves (HTTP to HTTP, HTTP to HTTPS, HTTPS to HTTPS). Don't send the Referer header for requests to less secure destinations (HTTPS to HTTP, HTTPS to file).
+    NoReferrerWhenDowngrade,
+    /// Send only the `origin` in the `Referer` header. For example, a document at `https://example.com/page.html` will send the referrer `https://example.com/`.
+    Origin,
+    /// When performing a [`Mode::SameOrigin`] request to the same protocol level (HTTP to HTTP, HTTPS to HTTPS), send the `origin`, `path`, and `querystring`. Send only the `origin` for cross origin requests and requests to less secure destinations (HTTPS to HTTP).
+    OriginWhenCrossOrigin,
+    /// Send the `origin`, `path`, and `querystring` when performing any request, regardless of security.
+    UnsafeUrl,
+    /// Send the origin, path, and `querystring` for [`Mode::SameOrigin`] requests. Don't send the Referer header for cross-origin requests.
+    SameOrigin,
+    /// Send only the `origin` when the protocol security level stays the same (HTTPS to HTTPS). Don't send the `Referer` header to less secure destinations (HTTPS to HTTP).
+    StrictOrigin,
+    #[default]
+    /// Send the `origin`, `path`, and `querystring` when performing a [`Mode::SameOrigin`] request. For cross-origin requests send the `origin` (only) when the protocol security level stays the same (HTTPS to HTTPS). Don't send the `Referer` header to less secure destinations (HTTPS to HTTP).
+    StrictOriginWhenCrossOrigin,
+}
+
+/// A request to be executed with the [Fetch API](https://developer.mozilla.org/en-US/docs/Web/API/Fetch_API).
+///
+/// The request is only described on the rust side, and can be executed multiple times.
+#[derive(Clone, Debug)]
+pub struct Fetch {
+    method: Method,
+    input: String,
+    base_url: Option<String>,
+    headers: HeaderMap,
+    body: Option<Body>,
+    cache: Option<Cache>,
+    credentials: Option<Credentials>,
+    integrity: Option<String>,
+    mode: Option<Mode>,
+    redirect: Option<Redirect>,
+    referrer: Option<String>,
+    referrer_policy: Option<ReferrerPolicy>,
+    timeout: Option<u32>,
+    retry: Option<RetryPolicy>,
+    transport: Option<Rc<dyn Transport>>,
+    clock: Option<Rc<dyn FetchClock>>,
+}
+
+impl Fetch {
+    #[inline]
+    pub fn new<U: ToString>(method: Method, url: U) -> Self {
+        Self {
+            method,
+            input: url.to_string(),
+            base_url: None,
+            headers: HeaderMap::new(),
+            body: None,
+            cache: None,
+            credentials: None,
+            integrity: None,
+            mode: None,
+            redirect: None,
+            referrer: None,
+            referrer_policy: None,
+            timeout: None,
+            retry: None,
+            transport: None,
+            clock: None,
+        }
+    }
+
+    #[inline]
+    pub fn get<U: ToString>(url: U) -> Self {
+        Self::new(Method::Get, url)
+    }
+
+    #[inline]
+    pub fn post<U: ToString>(url: U) -> Self {
+        Self::new(Method::Post, url)
+    }
+
+    #[inline]
+    pub fn head<U: ToString>(url: U) -> Self {
+        Self::new(Method::Head, url)
+    }
+
+    #[inline]
+    pub fn put<U: ToString>(url: U) -> Self {
+        Self::new(Method::Put, url)
+    }
+
+    #[inline]
+    pub fn delete<U: ToString>(url: U) -> Self {
+        Self::new(Method::Delete, url)
+    }
+
+    #[inline]
+    pub fn patch<U: ToString>(url: U) -> Self {
+        Self::new(Method::Patch, url)
+    }
+
+    #[inline]
+    pub fn method(&self) -> Method {
+        self.method
+    }
+
+    #[inline]
+    pub fn input(&self) -> &str {
+        &self.input
+    }
+
+    /// The url the request is sent to, [resolved](Fetch::with_base_url) against the base url, if any.
+    #[inline]
+    pub fn url(&self) -> String {
+        url::resolve(self.base_url.as_deref(), &self.input)
+    }
+
+    #[inline]
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    #[inline]
+    pub fn body(&self) -> Option<&Body> {
+        self.body.as_ref()
+    }
+
+    /// Builds the [`RequestInit`] passed to the browser, a new one is built for each attempt.
+    pub fn init(&self) -> RequestInit {
+        let init = RequestInit::new();
+        init.set_method(self.method.as_str());
+        init.set_headers(&self.headers.to_headers());
+
+        if let Some(body) = &self.body {
+            init.set_body(&body.to_js());
+        }
+
+        if let Some(cache) = self.cache {
+            init.set_cache(match cache {
+                Cache::Default => RequestCache::Default,
+                Cache::NoStore => RequestCache::NoStore,
+                Cache::Reload => RequestCache::Reload,
+                Cache::NoCache => RequestCache::NoCache,
+                Cache::ForceCache => RequestCache::ForceCache,
+                Cache::OnlyIfCached => RequestCache::OnlyIfCached,
+            });
+        }
+
+        if let Some(credentials) = self.credentials {
+            init.set_credentials(match credentials {
+                Credentials::Omit => RequestCredentials::Omit,
+                Credentials::SameOrigin => RequestCredentials::SameOrigin,
+                Credentials::Include => RequestCredentials::Include,
+            });
+        }
+
+        if let Some(integrity) = &self.integrity {
+            init.set_integrity(integrity);
+        }
+
+        if let Some(mode) = self.mode {
+            init.set_mode(match mode {
+                Mode::SameOrigin => RequestMode::SameOrigin,
+                Mode::NoCors => RequestMode::NoCors,
+                Mode::Cors => RequestMode::Cors,
+                Mode::Navigate => RequestMode::Navigate,
+            });
+        }
+
+        if let Some(redirect) = self.redirect {
+            init.set_redirect(match redirect {
+                Redirect::Follow => RequestRedirect::Follow,
+                Redirect::Error => RequestRedirect::Error,
+                Redirect::Manual => RequestRedirect::Manual,
+            });
+        }
+
+        if let Some(referrer) = &self.referrer {
+            init.set_referrer(referrer);
+        }
+
+        if let Some(referrer_policy) = self.referrer_policy {
+            init.set_referrer_policy(match referrer_policy {
+                ReferrerPolicy::NoReferrer => web_sys::ReferrerPolicy::NoReferrer,
+                ReferrerPolicy::NoReferrerWhenDowngrade => web_sys::ReferrerPolicy::NoReferrerWhenDowngrade,
+                ReferrerPolicy::Origin => web_sys::ReferrerPolicy::Origin,
+                ReferrerPolicy::OriginWhenCrossOrigin => web_sys::ReferrerPolicy::OriginWhenCrossOrigin,
+                ReferrerPolicy::UnsafeUrl => web_sys::ReferrerPolicy::UnsafeUrl,
+                ReferrerPolicy::SameOrigin => web_sys::ReferrerPolicy::SameOrigin,
+                ReferrerPolicy::StrictOrigin => web_sys::ReferrerPolicy::StrictOrigin,
+                ReferrerPolicy::StrictOriginWhenCrossOrigin => web_sys::ReferrerPolicy::StrictOriginWhenCrossOrigin,
+            });
+        }
+
+        init
+    }
+
+    /// Resolves the url of the request against `base_url`, unless it is absolute.
+    ///
+    /// For example, `users/1` and `/users/1` both resolve to `https://example.com/api/users/1` against
+    /// `https://example.com/api/`.
+    #[inline]
+    pub fn with_base_url<S: ToString>(&mut self, base_url: S) -> &mut Self {
+        self.base_url = Some(base_url.to_string());
+        self
+    }
+
+    /// Appends the given pairs to the query string of the url, percent-encoded.
+    #[inline]
+    pub fn with_query<K, V>(&mut self, pairs: &[(K, V)]) -> &mut Self
+    where
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        for (key, value) in pairs {
+            url::append_query(&mut self.input, key.as_ref(), value.as_ref());
+        }
+        self
+    }
+
+    /// Appends the fields of `query` to the query string of the url, percent-encoded.
+    ///
+    /// `query` must serialize to a map. Fields holding `None` are skipped, and the values of fields holding
+    /// sequences are each appended with the name of the field.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    pub fn with_query_struct<T: serde::Serialize>(&mut self, query: &T) -> Result<&mut Self, serde_json::Error> {
+        for (key, value) in url::struct_pairs(query)? {
+            url::append_query(&mut self.input, &key, &value);
+        }
+        Ok(self)
+    }
+
+    /// Replaces `{name}` in the url with `value`, percent-encoded.
+    ///
+    /// For example, `value` is `a/b` for `/files/{name}`, the url becomes `/files/a%2Fb`.
+    #[inline]
+    pub fn with_path_param<V: ToString>(&mut self, name: &str, value: V) -> &mut Self {
+        let pattern = alloc::format!("{{{name}}}");
+        self.input = self.input.replace(&pattern, &encode_uri_component(&value.to_string()));
+        self
+    }
+
+    /// Sets the body of the request.
+    ///
+    /// The `Content-Type` header is set to the content type of the body if it has one, unless the header
+    /// was already set.
+    pub fn with_body<B: RequestBody>(&mut self, body: &B) -> Result<&mut Self, B::Error> {
+        self.body = Some(body.to_body()?);
+        if let Some(content_type) = body.content_type() {
+            if !self.headers.contains("Content-Type") {
+                // Invalid content types are left to the browser to deal with.
+                self.headers.insert("Content-Type", content_type).ok();
+            }
+        }
+        Ok(self)
+    }
+
+    /// Sets the header `name` to `value`, replacing the previous headers with that name.
+    #[inline]
+    pub fn with_header(&mut self, name: &str, value: &str) -> Result<&mut Self, HeaderError> {
+        self.headers.insert(name, value)?;
+        Ok(self)
+    }
+
+    /// Sets all the given headers, see [`Fetch::with_header`].
+    #[inline]
+    pub fn with_headers<I, K, V>(&mut self, headers: I) -> Result<&mut Self, HeaderError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: AsRef<str>,
+    {
+        for (name, value) in headers {
+            self.headers.insert(name.as_ref(), value.as_ref())?;
+        }
+        Ok(self)
+    }
+
+    /// Sets the `Accept` header, the media types the response may have.
+    #[inline]
+    pub fn with_accept(&mut self, media_type: &str) -> Result<&mut Self, HeaderError> {
+        self.with_header("Accept", media_type)
+    }
+
+    /// Sets the `Authorization` header to use the `Bearer` scheme with `token`.
+    #[inline]
+    pub fn with_bearer_auth(&mut self, token: &str) -> Result<&mut Self, HeaderError> {
+        self.with_header("Authorization", &alloc::format!("Bearer {token}"))
+    }
+
+    /// Sets the `Authorization` header to use the `Basic` scheme with the given credentials.
+    #[inline]
+    pub fn with_basic_auth(&mut self, username: &str, password: Option<&str>) -> Result<&mut Self, HeaderError> {
+        let credentials = alloc::format!("{username}:{}", password.unwrap_or_default());
+        self.with_header(
+            "Authorization",
+            &alloc::format!("Basic {}", headers::base64(credentials.as_bytes())),
+        )
+    }
+
+    #[inline]
+    pub fn with_cache(&mut self, cache: Cache) -> &mut Self {
+        self.cache = Some(cache);
+        self
+    }
+
+    #[inline]
+    pub fn with_credentials(&mut self, credentials: Credentials) -> &mut Self {
+        self.credentials = Some(credentials);
+        self
+    }
+
+    #[inline]
+    pub fn with_integrity<S: AsRef<str>>(&mut self, integrity: S) -> &mut Self {
+        self.integrity = Some(integrity.as_ref().to_string());
+        self
+    }
+
+    #[inline]
+    pub fn with_mode(&mut self, mode: Mode) -> &mut Self {
+        self.mode = Some(mode);
+        self
+    }
+
+    #[inline]
+    pub fn with_redirect(&mut self, redirect: Redirect) -> &mut Self {
+        self.redirect = Some(redirect);
+        self
+    }
+
+    #[inline]
+    pub fn with_referrer<S: AsRef<str>>(&mut self, referrer: S) -> &mut Self {
+        self.referrer = Some(referrer.as_ref().to_string());
+        self
+    }
+
+    #[inline]
+    pub fn with_referrer_policy(&mut self, referrer_policy: ReferrerPolicy) -> &mut Self {
+        self.referrer_policy = Some(referrer_policy);
+        self
+    }
+
+    /// Aborts the request with [`FetchError::TimedOut`] if it does not complete within `ms` milliseconds,
+    /// reading the body of the response and retries included.
+    #[inline]
+    pub fn with_timeout(&mut self, ms: u32) -> &mut Self {
+        self.timeout = Some(ms);
+        self
+    }
+
+    /// Retries the request according to `retry`, when it fails.
+    #[inline]
+    pub fn with_retry(&mut self, retry: RetryPolicy) -> &mut Self {
+        self.retry = Some(retry);
+        self
+    }
+
+    /// Sends the request with `transport` instead of the [`BrowserTransport`], for example a
+    /// [`MockTransport`] in tests.
+    #[inline]
+    pub fn with_transport<T: Transport>(&mut self, transport: T) -> &mut Self {
+        self.transport = Some(Rc::new(transport));
+        self
+    }
+
+    /// Waits for the timeout and between retries with `clock` instead of the [`BrowserClock`], for example
+    /// a [`ManualClock`](crate::signal::ManualClock) in tests.
+    #[inline]
+    pub fn with_clock<C: Clock>(&mut self, clock: C) -> &mut Self {
+        self.clock = Some(Rc::new(clock));
+        self
+    }
+
+    /// Executes the request, and reads the body of the response as `R`.
+    #[inline]
+    pub fn execute<R: ResponseBody>(&self) -> impl Future<Output = Result<R, R::Error>> {
+        self.execute_abortable().1
+    }
+
+    /// Same as [`Fetch::execute`], also returning a handle to abort the request.
+    ///
+    /// The request is sent when the future is first polled.
+    pub fn execute_abortable<R: ResponseBody>(&self) -> (AbortHandle, impl Future<Output = Result<R, R::Error>>) {
+        let handle = AbortHandle::new();
+        let this = self.clone();
+
+        let future = {
+            let handle = handle.clone();
+            async move {
+                let execution = pin!(async {
+                    let response = this.send(&handle).await?;
+                    match R::from_response(&response).await {
+                        Err(_) if handle.is_aborted() => Err(handle.error(FetchError::Aborted).into()),
+                        result => result,
+                    }
+                });
+
+                let Some(ms) = this.timeout else {
+                    return execution.await;
+                };
+
+                match future::select(execution, this.clock().sleep(ms)).await {
+                    Either::Left((result, _)) => result,
+                    Either::Right(_) => {
+                        handle.time_out();
+                        Err(FetchError::TimedOut.into())
+                    }
+                }
+            }
+        };
+
+        (handle, future)
+    }
+
+    #[inline]
+    fn transport(&self) -> &dyn Transport {
+        self.transport.as_deref().unwrap_or(&BrowserTransport)
+    }
+
+    #[inline]
+    fn clock(&self) -> &dyn FetchClock {
+        self.clock.as_deref().unwrap_or(&BrowserClock)
+    }
+
+    /// Sends the request, retrying it as long as its retry policy allows it.
+    async fn send(&self, handle: &AbortHandle) -> Result<Response, FetchError> {
+        let (transport, clock) = (self.transport(), self.clock());
+        let max_attempts = self.retry.as_ref().map_or(1, RetryPolicy::max_attempts);
+        let mut attempt = 1;
+
+        loop {
+            let result = transport.send(self, handle).await.map_err(|error| handle.error(error));
+
+            let retry = self.retry.as_ref().filter(|_| attempt < max_attempts);
+            let delay = match (result, retry) {
+                (Ok(response), Some(retry)) if retry.retries_status(response.status()) => {
+                    retry.delay(attempt, response.headers().get("Retry-After"), clock.now())
+                }
+                (Ok(response), _) => return Ok(response),
+                (Err(FetchError::Network(_)), Some(retry)) => retry.backoff(attempt),
+                (Err(error), _) => return Err(error),
+            };
+
+            // Aborting the request cancels the wait.
+            if let Either::Right(_) = future::select(clock.sleep(delay), pin!(handle.aborted())).await {
+                return Err(handle.error(FetchError::Aborted));
+            }
+            attempt += 1;
+        }
+    }
+}
+
+/// A [`Clock`] that can be stored in a [`Fetch`], which needs it as a trait object.
+trait FetchClock {
+    fn now(&self) -> f64;
+
+    /// Returns a future that resolves after `ms` milliseconds.
+    fn sleep(&self, ms: u32) -> Pin<Box<dyn Future<Output = ()>>>;
+}
+
+impl<C: Clock> FetchClock for C {
+    #[inline]
+    fn now(&self) -> f64 {
+        Clock::now(self)
+    }
+
+    fn sleep(&self, ms: u32) -> Pin<Box<dyn Future<Output = ()>>> {
+        let state = Rc::new(RefCell::new((false, None::<Waker>)));
+        let timeout = self.set_timeout(ms, {
+            let state = state.clone();
+            move || {
+                let waker = {
+                    let mut state = state.borrow_mut();
+                    state.0 = true;
+                    state.1.take()
+                };
+                if let Some(waker) = waker {
+                    waker.wake();
+                }
+            }
+        });
+
+        Box::pin(future::poll_fn(move |cx| {
+            // The timeout is cancelled when the future is dropped.
+            let _timeout = &timeout;
+            let mut state = state.borrow_mut();
+            match state.0 {
+                true => Poll::Ready(()),
+                false => {
+                    state.1 = Some(cx.waker().clone());
+                    Poll::Pending
+                }
+            }
+        }))
+    }
+}
+
+impl fmt::Debug for dyn FetchClock {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Clock")
+    }
+}
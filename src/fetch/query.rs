@@ -0,0 +1,297 @@
+use core::any::Any;
+use core::cell::RefCell;
+use core::fmt;
+use core::future::Future;
+
+use alloc::boxed::Box;
+use alloc::collections::BTreeMap;
+use alloc::rc::{Rc, Weak};
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::signal::{BrowserClock, Clock, Signal, SignalMut};
+use crate::utils;
+
+use super::{Fetch, FetchError, Method, ResponseBody};
+
+/// Identifies the queries of a [`QueryCache`].
+#[derive(Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
+pub struct QueryKey {
+    pub method: Method,
+    pub url: String,
+}
+
+impl QueryKey {
+    #[inline]
+    pub fn new(method: Method, url: String) -> Self {
+        Self { method, url }
+    }
+}
+
+impl From<&Fetch> for QueryKey {
+    /// The key of a request, made of its method and [url](Fetch::url).
+    #[inline]
+    fn from(request: &Fetch) -> Self {
+        Self::new(request.method(), request.url())
+    }
+}
+
+/// The state of a query of a [`QueryCache`].
+#[derive(Debug)]
+pub struct Query<T, E = FetchError> {
+    /// The data of the latest successful fetch, kept while revalidating.
+    pub data: Option<T>,
+    /// The error of the latest fetch, if it failed.
+    pub error: Option<Rc<E>>,
+    /// Whether a fetch is in flight.
+    pub fetching: bool,
+}
+
+impl<T: Clone, E> Clone for Query<T, E> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            data: self.data.clone(),
+            error: self.error.clone(),
+            fetching: self.fetching,
+        }
+    }
+}
+
+impl<T, E> Default for Query<T, E> {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            data: None,
+            error: None,
+            fetching: false,
+        }
+    }
+}
+
+struct Entry {
+    /// The `SignalMut<Query<T, E>>` of the query.
+    query: Box<dyn Any>,
+    /// When the data was last fetched successfully, if it is not invalidated.
+    fetched_at: Option<f64>,
+    fetching: bool,
+    /// Whether the query was invalidated while in flight, so that it is fetched again once done.
+    invalidated: bool,
+    refetch: Rc<dyn Fn()>,
+}
+
+struct QueryCacheInner<C> {
+    clock: C,
+    stale_time: u32,
+    entries: RefCell<BTreeMap<QueryKey, Entry>>,
+}
+
+/// Caches the results of requests, shared as signals by all the components requesting them.
+///
+/// Queries are only fetched once at a time. Their cached data is returned right away, and
+/// revalidated in the background when it is older than the stale time
+/// ([stale-while-revalidate](https://developer.mozilla.org/en-US/docs/Web/HTTP/Headers/Cache-Control#stale-while-revalidate)).
+///
+/// Clones of a query cache share the same queries.
+pub struct QueryCache<C: Clock = BrowserClock>(Rc<QueryCacheInner<C>>);
+
+impl QueryCache {
+    /// Creates a cache whose data becomes stale `stale_time` milliseconds after being fetched.
+    #[inline]
+    pub fn new(stale_time: u32) -> Self {
+        Self::with_clock(BrowserClock, stale_time)
+    }
+}
+
+impl<C: Clock> QueryCache<C> {
+    /// Same as [`QueryCache::new`], using the given [`Clock`].
+    #[inline]
+    pub fn with_clock(clock: C, stale_time: u32) -> Self {
+        Self(Rc::new(QueryCacheInner {
+            clock,
+            stale_time,
+            entries: RefCell::default(),
+        }))
+    }
+
+    /// Executes `request`, unless it is cached and fresh or already in flight, and returns its query.
+    #[inline]
+    pub fn query<R>(&self, request: &Fetch) -> Signal<Query<R>>
+    where
+        R: ResponseBody<Error = FetchError> + Clone + 'static,
+    {
+        let request = request.clone();
+        self.query_with(QueryKey::from(&request), move || request.execute::<R>())
+    }
+
+    /// Same as [`QueryCache::query`], fetching the data of the query at `key` with `fetch`, for example
+    /// to execute a request with a [`Client`](super::Client).
+    ///
+    /// If the query at `key` holds data of another type, it is replaced.
+    pub fn query_with<T, E, F, Fut>(&self, key: QueryKey, fetch: F) -> Signal<Query<T, E>>
+    where
+        T: Clone + 'static,
+        E: 'static,
+        F: Fn() -> Fut + 'static,
+        Fut: Future<Output = Result<T, E>> + 'static,
+    {
+        let cached = self.0.entries.borrow().get(&key).and_then(|entry| {
+            let query = entry.query.downcast_ref::<SignalMut<Query<T, E>>>()?;
+            Some((query.clone(), entry.refetch.clone(), self.is_stale(entry)))
+        });
+
+        if let Some((query, refetch, stale)) = cached {
+            if stale {
+                refetch();
+            }
+            return (*query).clone();
+        }
+
+        let query = SignalMut::new(Query::default());
+        let refetch = self.refetch(key.clone(), query.clone(), fetch);
+        self.0.entries.borrow_mut().insert(
+            key,
+            Entry {
+                query: Box::new(query.clone()),
+                fetched_at: None,
+                fetching: false,
+                invalidated: false,
+                refetch: refetch.clone(),
+            },
+        );
+        refetch();
+        (*query).clone()
+    }
+
+    /// Marks the query at `key` as stale, and fetches it again.
+    #[inline]
+    pub fn invalidate(&self, key: &QueryKey) {
+        self.invalidate_where(|other| other == key);
+    }
+
+    /// Marks the queries whose key satisfies `predicate` as stale, and fetches them again.
+    ///
+    /// Queries in flight are fetched again once done, as their result may predate the invalidation.
+    ///
+    /// For example, `cache.invalidate_where(|key| key.url.starts_with("/api/users"))` after a user was edited.
+    pub fn invalidate_where<P>(&self, mut predicate: P)
+    where
+        P: FnMut(&QueryKey) -> bool,
+    {
+        let refetches: Vec<_> = self
+            .0
+            .entries
+            .borrow_mut()
+            .iter_mut()
+            .filter(|(key, _)| predicate(key))
+            .filter_map(|(_, entry)| {
+                entry.fetched_at = None;
+                entry.invalidated = entry.fetching;
+                (!entry.fetching).then(|| entry.refetch.clone())
+            })
+            .collect();
+
+        // Fetches that complete right away update the entries.
+        for refetch in refetches {
+            refetch();
+        }
+    }
+
+    /// Removes all the queries from the cache.
+    #[inline]
+    pub fn clear(&self) {
+        let entries = core::mem::take(&mut *self.0.entries.borrow_mut());
+        drop(entries);
+    }
+
+    #[inline]
+    fn is_stale(&self, entry: &Entry) -> bool {
+        entry
+            .fetched_at
+            .is_none_or(|fetched_at| self.0.clock.now() - fetched_at >= f64::from(self.0.stale_time))
+    }
+
+    /// Returns a closure fetching the query at `key`, unless it is already in flight.
+    fn refetch<T, E, F, Fut>(&self, key: QueryKey, query: SignalMut<Query<T, E>>, fetch: F) -> Rc<dyn Fn()>
+    where
+        T: Clone + 'static,
+        E: 'static,
+        F: Fn() -> Fut + 'static,
+        Fut: Future<Output = Result<T, E>> + 'static,
+    {
+        let weak = Rc::downgrade(&self.0);
+        Rc::new(move || {
+            if !start_fetching(&weak, &key) {
+                return;
+            }
+            // Queued when called by a subscriber of the query, for example to invalidate it.
+            query.update(|query| query.fetching = true);
+
+            let future = fetch();
+            let weak = weak.clone();
+            let key = key.clone();
+            let query = query.clone();
+            utils::spawn(async move {
+                let result = future.await;
+                let refetch = weak.upgrade().and_then(|inner| {
+                    let mut entries = inner.entries.borrow_mut();
+                    let entry = entries.get_mut(&key)?;
+                    entry.fetching = false;
+                    if core::mem::take(&mut entry.invalidated) {
+                        return Some(entry.refetch.clone());
+                    }
+                    if result.is_ok() {
+                        entry.fetched_at = Some(inner.clock.now());
+                    }
+                    None
+                });
+
+                query.update(move |query| {
+                    query.fetching = false;
+                    match result {
+                        Ok(data) => {
+                            query.data = Some(data);
+                            query.error = None;
+                        }
+                        Err(error) => query.error = Some(Rc::new(error)),
+                    }
+                });
+
+                if let Some(refetch) = refetch {
+                    refetch();
+                }
+            });
+        })
+    }
+}
+
+/// Marks the query at `key` as in flight, returning false if it already was.
+fn start_fetching<C>(weak: &Weak<QueryCacheInner<C>>, key: &QueryKey) -> bool {
+    let Some(inner) = weak.upgrade() else {
+        return false;
+    };
+    let mut entries = inner.entries.borrow_mut();
+    match entries.get_mut(key) {
+        Some(entry) if !entry.fetching => {
+            entry.fetching = true;
+            true
+        }
+        _ => false,
+    }
+}
+
+impl<C: Clock> Clone for QueryCache<C> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<C: Clock> fmt::Debug for QueryCache<C> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("QueryCache")
+            .field("stale_time", &self.0.stale_time)
+            .field("queries", &self.0.entries.borrow().keys().collect::<Vec<_>>())
+            .finish()
+    }
+}
@@ -0,0 +1,106 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::js_sys::{Promise, Uint8Array};
+use web_sys::wasm_bindgen::JsValue;
+
+use super::{FetchError, HeaderMap};
+
+/// Where the body of a [`Response`] is read from.
+#[derive(Clone, Debug)]
+enum Content {
+    Raw(web_sys::Response),
+    Bytes(Vec<u8>),
+}
+
+/// The response to a [`Fetch`](super::Fetch) request, whose body was not read yet.
+#[derive(Clone, Debug)]
+pub struct Response {
+    status: u16,
+    headers: HeaderMap,
+    content: Content,
+}
+
+/// Awaits a promise returned by one of the body methods of a response.
+#[inline]
+async fn read_body(promise: Result<Promise, JsValue>) -> Result<JsValue, FetchError> {
+    JsFuture::from(promise.map_err(FetchError::Body)?)
+        .await
+        .map_err(FetchError::Body)
+}
+
+impl Response {
+    /// Creates a response built on the rust side, for example to be returned by a
+    /// [`MockTransport`](super::MockTransport).
+    #[inline]
+    pub fn new<B: Into<Vec<u8>>>(status: u16, headers: HeaderMap, body: B) -> Self {
+        Self {
+            status,
+            headers,
+            content: Content::Bytes(body.into()),
+        }
+    }
+
+    /// Wraps a response returned by the browser.
+    #[inline]
+    pub fn from_raw(raw: web_sys::Response) -> Self {
+        Self {
+            status: raw.status(),
+            headers: HeaderMap::from_headers(&raw.headers()),
+            content: Content::Raw(raw),
+        }
+    }
+
+    /// The [status code](https://developer.mozilla.org/en-US/docs/Web/HTTP/Status) of the response.
+    #[inline]
+    pub fn status(&self) -> u16 {
+        self.status
+    }
+
+    /// Returns true iff the status of the response is in the range 200-299.
+    #[inline]
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    #[inline]
+    pub fn headers(&self) -> &HeaderMap {
+        &self.headers
+    }
+
+    /// The browser's response object, if the response was returned by the browser.
+    #[inline]
+    pub fn as_raw(&self) -> Option<&web_sys::Response> {
+        match &self.content {
+            Content::Raw(raw) => Some(raw),
+            Content::Bytes(_) => None,
+        }
+    }
+
+    /// The body of the response, if it was [built on the rust side](Response::new).
+    #[inline]
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match &self.content {
+            Content::Raw(_) => None,
+            Content::Bytes(bytes) => Some(bytes),
+        }
+    }
+
+    /// Reads the body of the response as text.
+    ///
+    /// Bodies built on the rust side that are not valid UTF-8 are decoded lossily, like the browser does.
+    pub async fn text(&self) -> Result<String, FetchError> {
+        match &self.content {
+            Content::Raw(raw) => Ok(read_body(raw.text()).await?.as_string().unwrap_or_default()),
+            Content::Bytes(bytes) => Ok(String::from_utf8_lossy(bytes).into_owned()),
+        }
+    }
+
+    /// Reads the body of the response as bytes.
+    pub async fn bytes(&self) -> Result<Vec<u8>, FetchError> {
+        match &self.content {
+            Content::Raw(raw) => Ok(Uint8Array::new(&read_body(raw.array_buffer()).await?).to_vec()),
+            Content::Bytes(bytes) => Ok(bytes.clone()),
+        }
+    }
+}
@@ -0,0 +1,112 @@
+use alloc::vec::Vec;
+use web_sys::js_sys::Date;
+
+use crate::utils;
+
+/// Describes when and how a failed [`Fetch`](super::Fetch) request is retried.
+///
+/// Requests are retried after network errors, and after responses whose status is one of the
+/// [retried statuses](RetryPolicy::with_statuses). Between attempts, the request waits for an
+/// exponentially growing delay, or for the delay asked by the `Retry-After` header of the response.
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: u32,
+    max_delay: u32,
+    jitter: bool,
+    statuses: Vec<u16>,
+}
+
+impl RetryPolicy {
+    /// Creates a policy making at most `max_attempts` attempts, the first one included.
+    ///
+    /// By default, the delay starts at 250 milliseconds and doubles with each attempt up to 30 seconds,
+    /// with jitter, and requests are retried on statuses 429, 502, 503 and 504.
+    #[inline]
+    pub fn new(max_attempts: u32) -> Self {
+        Self {
+            max_attempts,
+            base_delay: 250,
+            max_delay: 30_000,
+            jitter: true,
+            statuses: [429, 502, 503, 504].into(),
+        }
+    }
+
+    #[inline]
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Sets the delay before the second attempt, in milliseconds.
+    #[inline]
+    pub fn with_base_delay(mut self, ms: u32) -> Self {
+        self.base_delay = ms;
+        self
+    }
+
+    /// Sets the maximum delay between two attempts, in milliseconds.
+    #[inline]
+    pub fn with_max_delay(mut self, ms: u32) -> Self {
+        self.max_delay = ms;
+        self
+    }
+
+    /// Whether delays are randomized, to avoid many clients retrying at the same time.
+    #[inline]
+    pub fn with_jitter(mut self, jitter: bool) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the statuses of the responses that are retried.
+    #[inline]
+    pub fn with_statuses<I: IntoIterator<Item = u16>>(mut self, statuses: I) -> Self {
+        self.statuses = statuses.into_iter().collect();
+        self
+    }
+
+    /// Returns true iff a response with the given status is retried.
+    #[inline]
+    pub fn retries_status(&self, status: u16) -> bool {
+        self.statuses.contains(&status)
+    }
+
+    /// Returns the delay to wait for after the given failed `attempt`, starting from 1, in milliseconds.
+    ///
+    /// With jitter, the delay is picked at random between 0 and that value.
+    pub fn backoff(&self, attempt: u32) -> u32 {
+        let factor = 1u32.checked_shl(attempt.saturating_sub(1)).unwrap_or(u32::MAX);
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        match self.jitter {
+            true => (utils::random() * f64::from(delay)) as u32,
+            false => delay,
+        }
+    }
+
+    /// Returns the delay to wait for after the given failed `attempt`, honoring the value of the
+    /// `Retry-After` header of the response, if any, `now` being the current time in milliseconds.
+    pub(super) fn delay(&self, attempt: u32, retry_after: Option<&str>, now: f64) -> u32 {
+        retry_after
+            .and_then(|value| parse_retry_after(value, now))
+            .map_or_else(|| self.backoff(attempt), |delay| delay.min(self.max_delay))
+    }
+}
+
+impl Default for RetryPolicy {
+    #[inline]
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
+/// Parses the value of a `Retry-After` header, either a number of seconds or a date, into milliseconds.
+fn parse_retry_after(value: &str, now: f64) -> Option<u32> {
+    let value = value.trim();
+    if let Ok(seconds) = value.parse::<u32>() {
+        return Some(seconds.saturating_mul(1000));
+    }
+
+    let date = Date::parse(value);
+    (!date.is_nan()).then(|| (date - now).clamp(0.0, f64::from(u32::MAX)) as u32)
+}
@@ -0,0 +1,139 @@
+use core::pin::Pin;
+use core::task::{Context, Poll};
+
+use alloc::boxed::Box;
+use alloc::vec::Vec;
+use futures::stream::{self, Stream};
+use wasm_bindgen_futures::JsFuture;
+use web_sys::js_sys::{Reflect, Uint8Array};
+use web_sys::wasm_bindgen::{JsCast, JsValue};
+use web_sys::ReadableStreamDefaultReader;
+
+use crate::signal::{Signal, SignalMut};
+
+use super::{FetchError, Response, ResponseBody};
+
+/// The progress of the download of a [`ByteStream`].
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct Progress {
+    /// The number of bytes received so far.
+    pub received: u64,
+    /// The size of the body, if the response has a `Content-Length` header.
+    pub total: Option<u64>,
+}
+
+impl Progress {
+    /// Returns the fraction of the body received so far, between 0 and 1, if the size of the body is known.
+    #[inline]
+    pub fn ratio(&self) -> Option<f64> {
+        match self.total? {
+            0 => Some(1.0),
+            total => Some((self.received as f64 / total as f64).min(1.0)),
+        }
+    }
+}
+
+/// The body of a response, read chunk by chunk as it is downloaded.
+///
+/// The download is cancelled when the stream is dropped. Note that the [timeout](super::Fetch::with_timeout)
+/// of the request does not apply to the chunks read after the stream was returned.
+pub struct ByteStream {
+    chunks: Pin<Box<dyn Stream<Item = Result<Vec<u8>, FetchError>>>>,
+    reader: Option<ReadableStreamDefaultReader>,
+    progress: SignalMut<Progress>,
+}
+
+impl ByteStream {
+    /// The progress of the download, updated as chunks are read.
+    #[inline]
+    pub fn progress(&self) -> &Signal<Progress> {
+        &self.progress
+    }
+}
+
+/// Reads the next chunk from `reader`, if the body is not over.
+async fn read_chunk(reader: &ReadableStreamDefaultReader) -> Result<Option<Vec<u8>>, FetchError> {
+    let result = JsFuture::from(reader.read()).await.map_err(FetchError::Body)?;
+    let field = |name: &str| Reflect::get(&result, &JsValue::from_str(name)).map_err(FetchError::Body);
+
+    if field("done")?.is_truthy() {
+        return Ok(None);
+    }
+    Ok(Some(field("value")?.unchecked_into::<Uint8Array>().to_vec()))
+}
+
+impl ResponseBody for ByteStream {
+    type Error = FetchError;
+
+    async fn from_response(response: &Response) -> Result<Self, Self::Error> {
+        let total = response.headers().content_length();
+
+        let Some(raw) = response.as_raw() else {
+            let bytes = response.as_bytes().unwrap_or_default().to_vec();
+            return Ok(Self {
+                progress: SignalMut::new(Progress {
+                    received: 0,
+                    total: total.or(Some(bytes.len() as u64)),
+                }),
+                chunks: Box::pin(stream::iter((!bytes.is_empty()).then_some(Ok(bytes)))),
+                reader: None,
+            });
+        };
+
+        let progress = SignalMut::new(Progress { received: 0, total });
+
+        // Responses to HEAD requests, for example, have no body.
+        let Some(body) = raw.body() else {
+            return Ok(Self {
+                chunks: Box::pin(stream::empty()),
+                reader: None,
+                progress,
+            });
+        };
+
+        let reader = body.get_reader().unchecked_into::<ReadableStreamDefaultReader>();
+        let chunks = stream::unfold(Some(reader.clone()), |reader| async move {
+            let reader = reader?;
+            match read_chunk(&reader).await {
+                Ok(Some(chunk)) => Some((Ok(chunk), Some(reader))),
+                Ok(None) => None,
+                // The stream ends after an error.
+                Err(error) => Some((Err(error), None)),
+            }
+        });
+
+        Ok(Self {
+            chunks: Box::pin(chunks),
+            reader: Some(reader),
+            progress,
+        })
+    }
+}
+
+impl Stream for ByteStream {
+    type Item = Result<Vec<u8>, FetchError>;
+
+    #[inline]
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let poll = self.chunks.as_mut().poll_next(cx);
+        match &poll {
+            Poll::Ready(Some(Ok(chunk))) => {
+                let received = chunk.len() as u64;
+                self.progress.mutate(|progress| progress.received += received);
+            }
+            // Nothing to cancel anymore.
+            Poll::Ready(None) => drop(self.reader.take()),
+            _ => (),
+        }
+        poll
+    }
+}
+
+impl Drop for ByteStream {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(reader) = self.reader.take() {
+            _ = reader.cancel();
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use core::fmt;
+use core::future::Future;
+use core::pin::Pin;
+
+use alloc::boxed::Box;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::wasm_bindgen::JsCast;
+
+use super::{AbortHandle, Fetch, FetchError, Response};
+
+/// The future returned by [`Transport::send`].
+pub type TransportFuture = Pin<Box<dyn Future<Output = Result<Response, FetchError>>>>;
+
+/// Sends the requests of a [`Fetch`], see [`Fetch::with_transport`].
+///
+/// Requests are sent by the [`BrowserTransport`] by default.
+pub trait Transport: fmt::Debug + 'static {
+    /// Sends `request` once, the retries being handled by the [`Fetch`] itself.
+    ///
+    /// The request should fail with [`FetchError::Aborted`] once `abort` is aborted.
+    fn send(&self, request: &Fetch, abort: &AbortHandle) -> TransportFuture;
+}
+
+/// Sends requests with the [`fetch`](https://developer.mozilla.org/en-US/docs/Web/API/fetch) function
+/// of the browser.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct BrowserTransport;
+
+impl Transport for BrowserTransport {
+    fn send(&self, request: &Fetch, abort: &AbortHandle) -> TransportFuture {
+        let init = request.init();
+        init.set_signal(Some(&abort.signal()));
+        let promise = web_sys::window()
+            .unwrap()
+            .fetch_with_str_and_init(&request.url(), &init);
+
+        Box::pin(async move {
+            let response = JsFuture::from(promise).await.map_err(FetchError::Network)?;
+            Ok(Response::from_raw(response.unchecked_into()))
+        })
+    }
+}
@@ -0,0 +1,99 @@
+use core::fmt::Write;
+
+use alloc::string::String;
+#[cfg(feature = "json")]
+use alloc::vec::Vec;
+
+/// Percent-encodes `s` like JavaScript's [`encodeURIComponent`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/encodeURIComponent),
+/// so that it can be used as a path segment or in a query string.
+pub fn encode_uri_component(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'!' | b'~' | b'*' | b'\'' | b'(' | b')' => {
+                encoded.push(byte as char)
+            }
+            _ => _ = write!(encoded, "%{byte:02X}"),
+        }
+    }
+    encoded
+}
+
+/// Encodes `s` for an `application/x-www-form-urlencoded` body, like [`URLSearchParams`](https://developer.mozilla.org/en-US/docs/Web/API/URLSearchParams) does.
+pub(super) fn encode_form_component(s: &str) -> String {
+    let mut encoded = String::with_capacity(s.len());
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'*' | b'-' | b'.' | b'_' => encoded.push(byte as char),
+            b' ' => encoded.push('+'),
+            _ => _ = write!(encoded, "%{byte:02X}"),
+        }
+    }
+    encoded
+}
+
+/// Appends the pair `key=value` to the query string of `url`, before its fragment if any.
+pub(super) fn append_query(url: &mut String, key: &str, value: &str) {
+    let fragment = url.find('#').map(|index| url.split_off(index));
+    url.push(if url.contains('?') { '&' } else { '?' });
+    url.push_str(&encode_uri_component(key));
+    url.push('=');
+    url.push_str(&encode_uri_component(value));
+    url.extend(fragment);
+}
+
+/// Returns true iff `url` is absolute, i.e. has a scheme or is protocol-relative.
+fn is_absolute(url: &str) -> bool {
+    url.starts_with("//")
+        || url.split_once(':').is_some_and(|(scheme, _)| {
+            scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+                && scheme.chars().all(|c| c.is_ascii_alphanumeric() || "+-.".contains(c))
+        })
+}
+
+/// Resolves `url` against `base`, unless it is absolute.
+pub(super) fn resolve(base: Option<&str>, url: &str) -> String {
+    match base {
+        Some(base) if !is_absolute(url) => {
+            let mut resolved = String::from(base.trim_end_matches('/'));
+            if !url.is_empty() && !url.starts_with(['?', '#']) {
+                resolved.push('/');
+            }
+            resolved.push_str(url.trim_start_matches('/'));
+            resolved
+        }
+        _ => url.into(),
+    }
+}
+
+/// Serializes `value` into a list of key-value pairs, for a query string or a form.
+///
+/// `value` must serialize to a map. Fields holding `None` are skipped, and the values of fields holding
+/// sequences are each paired with the name of the field.
+#[cfg(feature = "json")]
+pub(super) fn struct_pairs<T: serde::Serialize>(value: &T) -> Result<Vec<(String, String)>, serde_json::Error> {
+    use alloc::string::ToString;
+    use serde::ser::Error;
+    use serde_json::Value;
+
+    let Value::Object(fields) = serde_json::to_value(value)? else {
+        return Err(serde_json::Error::custom("expected a map"));
+    };
+
+    let mut pairs = Vec::new();
+    for (key, value) in fields {
+        let values = match value {
+            Value::Array(values) => values,
+            value => alloc::vec![value],
+        };
+        for value in values {
+            match value {
+                Value::String(string) => pairs.push((key.clone(), string)),
+                Value::Null => (),
+                Value::Bool(_) | Value::Number(_) => pairs.push((key.clone(), value.to_string())),
+                Value::Array(_) | Value::Object(_) => return Err(serde_json::Error::custom("values can't be nested")),
+            }
+        }
+    }
+    Ok(pairs)
+}
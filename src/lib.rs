@@ -3,6 +3,9 @@
 #![no_std]
 
 extern crate alloc;
+// Tests run natively and on several threads, `thread_local!` is needed there.
+#[cfg(not(target_arch = "wasm32"))]
+extern crate std;
 
 pub mod attribute;
 pub mod component;
@@ -14,6 +17,9 @@ pub mod html;
 #[cfg_attr(docsrs, doc(cfg(feature = "logger")))]
 pub mod logger;
 pub mod signal;
+#[cfg(feature = "sse")]
+#[cfg_attr(docsrs, doc(cfg(feature = "sse")))]
+pub mod sse;
 pub mod utils;
 pub mod view;
 
@@ -0,0 +1,153 @@
+//! Sources of time, used by the time-based combinators of signals.
+
+use core::cell::RefCell;
+
+use alloc::boxed::Box;
+use alloc::rc::{Rc, Weak};
+use alloc::vec::Vec;
+use web_sys::js_sys::Date;
+
+use crate::utils::Timeout;
+
+/// A source of time and timeouts, used by time-based combinators such as [`Signal::debounce_with`](super::Signal::debounce_with).
+pub trait Clock: 'static {
+    /// A scheduled callback, which is cancelled when dropped.
+    type Timeout: 'static;
+
+    /// Returns the current time, in milliseconds.
+    fn now(&self) -> f64;
+
+    /// Schedules `callback` to be called after `ms` milliseconds.
+    fn set_timeout<F>(&self, ms: u32, callback: F) -> Self::Timeout
+    where
+        F: FnOnce() + 'static;
+}
+
+/// The clock of the browser, using [`Date.now()`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Date/now)
+/// and [`setTimeout`](https://developer.mozilla.org/en-US/docs/Web/API/setTimeout).
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub struct BrowserClock;
+
+impl Clock for BrowserClock {
+    type Timeout = Timeout;
+
+    #[inline]
+    fn now(&self) -> f64 {
+        Date::now()
+    }
+
+    #[inline]
+    fn set_timeout<F>(&self, ms: u32, callback: F) -> Self::Timeout
+    where
+        F: FnOnce() + 'static,
+    {
+        Timeout::new(ms, callback)
+    }
+}
+
+/// A scheduled callback of a [`ManualClock`].
+struct Scheduled {
+    id: usize,
+    due: f64,
+    callback: Box<dyn FnOnce()>,
+}
+
+#[derive(Default)]
+struct ManualClockInner {
+    now: f64,
+    next_id: usize,
+    scheduled: Vec<Scheduled>,
+}
+
+/// A clock whose time only moves forward when told to, useful to test time-based code natively.
+///
+/// Clones of a manual clock share the same time.
+#[derive(Clone, Default)]
+pub struct ManualClock(Rc<RefCell<ManualClockInner>>);
+
+impl ManualClock {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Moves the time forward by `ms` milliseconds, calling the callbacks that are due in order.
+    pub fn advance(&self, ms: u32) {
+        let target = self.0.borrow().now + f64::from(ms);
+
+        loop {
+            let mut inner = self.0.borrow_mut();
+            let next = inner
+                .scheduled
+                .iter()
+                .enumerate()
+                .filter(|(_, scheduled)| scheduled.due <= target)
+                .min_by(|(_, a), (_, b)| a.due.total_cmp(&b.due).then(a.id.cmp(&b.id)))
+                .map(|(index, _)| index);
+
+            match next {
+                Some(index) => {
+                    let scheduled = inner.scheduled.remove(index);
+                    inner.now = scheduled.due;
+                    // Callbacks may schedule more callbacks.
+                    drop(inner);
+                    (scheduled.callback)();
+                }
+                _ => {
+                    inner.now = target;
+                    break;
+                }
+            }
+        }
+    }
+}
+
+impl Clock for ManualClock {
+    type Timeout = ManualTimeout;
+
+    #[inline]
+    fn now(&self) -> f64 {
+        self.0.borrow().now
+    }
+
+    fn set_timeout<F>(&self, ms: u32, callback: F) -> Self::Timeout
+    where
+        F: FnOnce() + 'static,
+    {
+        let mut inner = self.0.borrow_mut();
+        let id = inner.next_id;
+        inner.next_id += 1;
+        let due = inner.now + f64::from(ms);
+        inner.scheduled.push(Scheduled {
+            id,
+            due,
+            callback: Box::new(callback),
+        });
+
+        ManualTimeout {
+            id,
+            clock: Rc::downgrade(&self.0),
+        }
+    }
+}
+
+/// A callback scheduled on a [`ManualClock`], cancelled when dropped.
+pub struct ManualTimeout {
+    id: usize,
+    clock: Weak<RefCell<ManualClockInner>>,
+}
+
+impl Drop for ManualTimeout {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(clock) = self.clock.upgrade() {
+            let mut inner = clock.borrow_mut();
+            if let Some(index) = inner.scheduled.iter().position(|scheduled| scheduled.id == self.id) {
+                let scheduled = inner.scheduled.remove(index);
+                // The callback may hold timeouts of its own.
+                drop(inner);
+                drop(scheduled);
+            }
+        }
+    }
+}
@@ -0,0 +1,116 @@
+use core::any::Any;
+use core::cell::RefCell;
+use core::mem;
+
+use alloc::boxed::Box;
+use alloc::rc::{Rc, Weak};
+use alloc::vec::Vec;
+
+use crate::utils::local;
+
+use super::raw::RawSignal;
+use super::Signal;
+
+local! {
+    /// The signals read during the evaluation of the innermost computed signal, if any.
+    static CURRENT: RefCell<Option<Vec<Dependency>>> = RefCell::new(None);
+}
+
+type Rerun = Rc<dyn Fn()>;
+
+/// A signal read during the evaluation of a computed signal.
+struct Dependency {
+    /// The address of the signal, to tell signals apart.
+    id: *const (),
+    /// Subscribes `rerun` to the signal, returning a guard unsubscribing when dropped.
+    subscribe: Box<dyn FnOnce(Rerun) -> Box<dyn Any>>,
+}
+
+/// Records that `signal` was read, if a computed signal is being evaluated.
+pub(super) fn track<T>(signal: &Signal<T>) {
+    CURRENT.with(|current| {
+        let mut current = current.borrow_mut();
+        let Some(deps) = current.as_mut() else {
+            return;
+        };
+
+        let id = Rc::as_ptr(signal.raw()) as *const ();
+        if deps.iter().any(|dep| dep.id == id) {
+            return;
+        }
+
+        let signal = signal.clone();
+        deps.push(Dependency {
+            id,
+            subscribe: Box::new(move |rerun| {
+                // The signal notifies new subscribers of its current value, which was just read.
+                let mut skip = signal.raw().is_init();
+                let unsub = signal.for_each(move |_| {
+                    if !mem::take(&mut skip) {
+                        rerun();
+                    }
+                });
+                Box::new(unsub.droppable())
+            }),
+        });
+    });
+}
+
+/// The state of a computed signal, owned by the signal itself.
+pub(super) struct Computed<T: 'static, F> {
+    signal: Weak<RawSignal<T>>,
+    compute: RefCell<F>,
+    /// The signals read during the last evaluation, with their subscription guard.
+    subscriptions: RefCell<Vec<(*const (), Box<dyn Any>)>>,
+}
+
+impl<T, F> Computed<T, F>
+where
+    F: FnMut() -> T + 'static,
+{
+    pub(super) fn new(signal: &Signal<T>, compute: F) -> Rc<Self> {
+        let this = Rc::new(Self {
+            signal: Rc::downgrade(signal.raw()),
+            compute: RefCell::new(compute),
+            subscriptions: RefCell::default(),
+        });
+        this.run();
+        this
+    }
+
+    /// Evaluates the signal again, and subscribes to the signals that were read if they changed.
+    fn run(self: &Rc<Self>) {
+        let Some(raw) = self.signal.upgrade() else {
+            return;
+        };
+
+        // The evaluation may have caused a dependency to change.
+        let Ok(mut compute) = self.compute.try_borrow_mut() else {
+            return;
+        };
+
+        let previous = CURRENT.with(|current| current.replace(Some(Vec::new())));
+        let value = compute();
+        let deps = CURRENT.with(|current| current.replace(previous)).unwrap_or_default();
+        drop(compute);
+
+        let mut subscriptions = self.subscriptions.take();
+        subscriptions.retain(|(id, _)| deps.iter().any(|dep| dep.id == *id));
+
+        let weak = Rc::downgrade(self);
+        let rerun: Rerun = Rc::new(move || {
+            if let Some(this) = weak.upgrade() {
+                this.run();
+            }
+        });
+
+        for dep in deps {
+            if subscriptions.iter().all(|(id, _)| *id != dep.id) {
+                subscriptions.push((dep.id, (dep.subscribe)(rerun.clone())));
+            }
+        }
+
+        *self.subscriptions.borrow_mut() = subscriptions;
+        raw.set(value);
+    }
+}
@@ -0,0 +1,201 @@
+//! Introspection of the graph of live signals, to debug why a UI does not update.
+//!
+//! Each signal is a node of the graph, and each signal derived from another (e.g. with
+//! [`Signal::map`](super::Signal::map)) is linked to its parent. Name the signals you are
+//! interested in with [`Signal::named`](super::Signal::named).
+
+use core::any;
+use core::cell::RefCell;
+use core::fmt::Write;
+
+use alloc::collections::BTreeMap;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::utils::local;
+
+struct NodeInfo {
+    name: Option<String>,
+    type_name: &'static str,
+    subscribers: usize,
+    parents: Vec<usize>,
+}
+
+struct Graph {
+    next_id: usize,
+    nodes: BTreeMap<usize, NodeInfo>,
+}
+
+local! {
+    static GRAPH: RefCell<Graph> = RefCell::new(Graph {
+        next_id: 0,
+        nodes: BTreeMap::new(),
+    });
+}
+
+/// The node of a signal in the graph, removed when dropped.
+pub(super) struct Node(usize);
+
+impl Node {
+    pub(super) fn new<T>() -> Self {
+        GRAPH.with(|graph| {
+            let mut graph = graph.borrow_mut();
+            let id = graph.next_id;
+            graph.next_id += 1;
+            graph.nodes.insert(
+                id,
+                NodeInfo {
+                    name: None,
+                    type_name: any::type_name::<T>(),
+                    subscribers: 0,
+                    parents: Vec::new(),
+                },
+            );
+            Self(id)
+        })
+    }
+
+    fn with<F: FnOnce(&mut NodeInfo)>(&self, f: F) {
+        GRAPH.with(|graph| graph.borrow_mut().nodes.get_mut(&self.0).map(f));
+    }
+
+    pub(super) fn set_name(&self, name: &str) {
+        self.with(|node| node.name = Some(name.to_string()));
+    }
+
+    pub(super) fn add_subscriber(&self) {
+        self.with(|node| node.subscribers += 1);
+    }
+
+    pub(super) fn remove_subscriber(&self) {
+        self.with(|node| node.subscribers -= 1);
+    }
+
+    pub(super) fn add_parent(&self, parent: &Node) {
+        self.with(|node| node.parents.push(parent.0));
+    }
+}
+
+impl Drop for Node {
+    fn drop(&mut self) {
+        GRAPH.with(|graph| graph.borrow_mut().nodes.remove(&self.0));
+    }
+}
+
+/// A live signal, as seen by [`signals`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct SignalInfo {
+    /// A unique identifier of the signal.
+    pub id: usize,
+    /// The name given with [`Signal::named`](super::Signal::named).
+    pub name: Option<String>,
+    /// The type of the values of the signal.
+    pub type_name: &'static str,
+    /// The number of active subscribers of the signal.
+    pub subscribers: usize,
+    /// The identifiers of the live signals this one is derived from.
+    pub parents: Vec<usize>,
+}
+
+impl SignalInfo {
+    /// The name of the signal if any, else its identifier.
+    fn label(&self) -> String {
+        match &self.name {
+            Some(name) => name.clone(),
+            _ => alloc::format!("#{}", self.id),
+        }
+    }
+}
+
+/// Returns all the live signals, ordered by creation.
+pub fn signals() -> Vec<SignalInfo> {
+    GRAPH.with(|graph| {
+        let graph = graph.borrow();
+        graph
+            .nodes
+            .iter()
+            .map(|(&id, node)| SignalInfo {
+                id,
+                name: node.name.clone(),
+                type_name: node.type_name,
+                subscribers: node.subscribers,
+                parents: node
+                    .parents
+                    .iter()
+                    .copied()
+                    .filter(|parent| graph.nodes.contains_key(parent))
+                    .collect(),
+            })
+            .collect()
+    })
+}
+
+/// Returns the graph of live signals in the [DOT](https://graphviz.org/doc/info/lang.html) language.
+pub fn to_dot() -> String {
+    let mut dot = String::from("digraph signals {\n");
+    for signal in signals() {
+        let label = alloc::format!(
+            "{}: {} ({} subscribers)",
+            signal.label(),
+            signal.type_name,
+            signal.subscribers
+        );
+        _ = writeln!(dot, "    {} [label={}];", signal.id, quote(&label));
+        for parent in signal.parents {
+            _ = writeln!(dot, "    {} -> {};", parent, signal.id);
+        }
+    }
+    dot.push('}');
+    dot
+}
+
+/// Returns the live signals as a JSON array, each signal being an object with the same fields as
+/// [`SignalInfo`].
+pub fn to_json() -> String {
+    let mut json = String::from("[");
+    for (i, signal) in signals().into_iter().enumerate() {
+        if i != 0 {
+            json.push(',');
+        }
+        let name = signal.name.as_deref().map_or_else(|| "null".to_string(), quote);
+        _ = write!(
+            json,
+            r#"{{"id":{},"name":{},"type_name":{},"subscribers":{},"parents":{:?}}}"#,
+            signal.id,
+            name,
+            quote(signal.type_name),
+            signal.subscribers,
+            signal.parents,
+        );
+    }
+    json.push(']');
+    json
+}
+
+/// Logs the graph of live signals in the DOT language, see [`to_dot`].
+#[inline]
+pub fn log_dot() {
+    log::info!("{}", to_dot());
+}
+
+/// Logs the live signals as JSON, see [`to_json`].
+#[inline]
+pub fn log_json() {
+    log::info!("{}", to_json());
+}
+
+/// Quotes and escapes `s`, for both DOT and JSON.
+fn quote(s: &str) -> String {
+    let mut quoted = String::from("\"");
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            c if c.is_control() => _ = write!(quoted, "\\u{:04x}", c as u32),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
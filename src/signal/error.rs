@@ -1,11 +1,19 @@
 use core::fmt;
 
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Debug)]
-pub struct SignalUpdatingError;
+pub enum SignalUpdatingError {
+    Updating,
+    /// The values set while notifying kept triggering new updates, past the
+    /// [maximum update depth](super::set_max_update_depth).
+    InfiniteLoop,
+}
 
 impl fmt::Display for SignalUpdatingError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "signal is already updating")
+        match self {
+            Self::Updating => write!(f, "signal is already updating"),
+            Self::InfiniteLoop => write!(f, "signal updates itself in an infinite loop"),
+        }
     }
 }
 
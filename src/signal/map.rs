@@ -0,0 +1,257 @@
+use core::ops::Deref;
+
+use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
+
+use super::raw::{RawCollection, RawSignal};
+use super::{CollectionUnsubscriber, Scope, Signal, SignalGetError};
+
+type RawSignalMap<K, V> = RawCollection<BTreeMap<K, V>, MapDiff<K, V>>;
+
+pub type SignalMapUnsubscriber<K, V> = CollectionUnsubscriber<BTreeMap<K, V>, MapDiff<K, V>>;
+
+/// A change made to a [`SignalMap`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum MapDiff<K, V> {
+    /// The whole map was replaced. This is also the first diff received by new subscribers.
+    Replace(BTreeMap<K, V>),
+    /// A value was inserted at a key that was previously vacant.
+    Insert(K, V),
+    /// The value at an occupied key was replaced.
+    Update(K, V),
+    /// The value at the given key was removed.
+    Remove(K),
+    /// The map was cleared.
+    Clear,
+}
+
+impl<K: Ord + Clone, V: Clone> MapDiff<K, V> {
+    /// Applies the diff to `map`.
+    pub fn apply_to(&self, map: &mut BTreeMap<K, V>) {
+        match self {
+            Self::Replace(entries) => map.clone_from(entries),
+            Self::Insert(key, value) | Self::Update(key, value) => drop(map.insert(key.clone(), value.clone())),
+            Self::Remove(key) => drop(map.remove(key)),
+            Self::Clear => map.clear(),
+        }
+    }
+
+    /// Returns true iff the diff may have changed the value at `key`.
+    #[inline]
+    pub fn affects(&self, key: &K) -> bool {
+        match self {
+            Self::Replace(_) | Self::Clear => true,
+            Self::Insert(other, _) | Self::Update(other, _) | Self::Remove(other) => key == other,
+        }
+    }
+}
+
+/// An ordered map whose changes are broadcasted to its subscribers as [`MapDiff`]s.
+#[repr(transparent)]
+pub struct SignalMap<K: 'static, V: 'static>(Rc<RawSignalMap<K, V>>);
+
+impl<K: Ord + Clone, V: Clone> SignalMap<K, V> {
+    #[inline]
+    fn new_from_raw(raw: RawSignalMap<K, V>) -> Self {
+        Self(Rc::new(raw))
+    }
+
+    #[inline]
+    fn raw(&self) -> &Rc<RawSignalMap<K, V>> {
+        &self.0
+    }
+
+    #[inline]
+    pub fn try_len(&self) -> Result<usize, SignalGetError> {
+        self.raw().try_with(BTreeMap::len)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.try_len().unwrap()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    pub fn try_get(&self, key: &K) -> Result<Option<V>, SignalGetError> {
+        self.raw().try_with(|entries| entries.get(key).cloned())
+    }
+
+    #[inline]
+    pub fn get(&self, key: &K) -> Option<V> {
+        self.try_get(key).unwrap()
+    }
+
+    #[inline]
+    pub fn try_to_map(&self) -> Result<BTreeMap<K, V>, SignalGetError> {
+        self.raw().try_with(BTreeMap::clone)
+    }
+
+    #[inline]
+    pub fn to_map(&self) -> BTreeMap<K, V> {
+        self.try_to_map().unwrap()
+    }
+
+    /// Returns a signal holding the value at `key`, if any.
+    ///
+    /// Its subscribers are only notified when the value at `key` changes.
+    pub fn signal(&self, key: K) -> Signal<Option<V>>
+    where
+        V: PartialEq,
+    {
+        let signal = Signal::new_from_raw(RawSignal::new(None));
+        let weak = Rc::downgrade(signal.raw());
+
+        self.for_each_inner(move |diff, unsub| {
+            let Some(raw) = weak.upgrade() else {
+                unsub.unsubscribe();
+                return;
+            };
+
+            if !diff.affects(&key) {
+                return;
+            }
+
+            let value = match diff {
+                MapDiff::Replace(entries) => entries.get(&key).cloned(),
+                MapDiff::Insert(_, value) | MapDiff::Update(_, value) => Some(value.clone()),
+                MapDiff::Remove(_) | MapDiff::Clear => None,
+            };
+
+            raw.set_if_changed(&value);
+        });
+
+        signal
+    }
+
+    #[inline]
+    pub fn for_each<F>(&self, notify: F) -> SignalMapUnsubscriber<K, V>
+    where
+        F: FnMut(&MapDiff<K, V>) + 'static,
+    {
+        let id = self
+            .raw()
+            .raw_for_each(|_| notify, |entries| MapDiff::Replace(entries.clone()));
+        SignalMapUnsubscriber::new(Rc::downgrade(self.raw()), id)
+    }
+
+    #[inline]
+    pub fn for_each_inner<F>(&self, mut notify: F)
+    where
+        F: FnMut(&MapDiff<K, V>, &mut SignalMapUnsubscriber<K, V>) + 'static,
+    {
+        let weak = Rc::downgrade(self.raw());
+        self.raw().raw_for_each(
+            |id| {
+                let mut unsub = SignalMapUnsubscriber::new(weak, id);
+                move |diff| notify(diff, &mut unsub)
+            },
+            |entries| MapDiff::Replace(entries.clone()),
+        );
+    }
+
+    /// See [`Signal::for_each_forever`](super::Signal::for_each_forever).
+    #[inline]
+    pub fn for_each_forever<F>(&self, notify: F)
+    where
+        F: FnMut(&MapDiff<K, V>) + 'static,
+    {
+        Scope::adopt(self.for_each(notify));
+    }
+}
+
+impl<K, V> Clone for SignalMap<K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+/// A mutable [`SignalMap`].
+///
+/// # Panics
+///
+/// All methods modifying the map panic if it is already being updated, for example when
+/// called by one of its subscribers.
+#[repr(transparent)]
+pub struct SignalMapMut<K: 'static, V: 'static>(SignalMap<K, V>);
+
+impl<K: Ord + Clone, V: Clone> SignalMapMut<K, V> {
+    #[inline]
+    pub fn new(entries: BTreeMap<K, V>) -> Self {
+        Self(SignalMap::new_from_raw(RawCollection::new(entries)))
+    }
+
+    /// Inserts `value` at `key`, returning the previous value at `key`, if any.
+    #[inline]
+    pub fn insert(&self, key: K, value: V) -> Option<V> {
+        let mut previous = None;
+        self.raw()
+            .try_update(|entries| {
+                previous = entries.insert(key.clone(), value.clone());
+                Some(match previous {
+                    Some(_) => MapDiff::Update(key, value),
+                    _ => MapDiff::Insert(key, value),
+                })
+            })
+            .unwrap();
+        previous
+    }
+
+    /// Removes the value at `key`, returning it if there was one.
+    #[inline]
+    pub fn remove(&self, key: &K) -> Option<V> {
+        let mut removed = None;
+        self.raw()
+            .try_update(|entries| {
+                removed = entries.remove(key);
+                removed.as_ref().map(|_| MapDiff::Remove(key.clone()))
+            })
+            .unwrap();
+        removed
+    }
+
+    #[inline]
+    pub fn clear(&self) {
+        self.raw()
+            .try_update(|entries| {
+                entries.clear();
+                Some(MapDiff::Clear)
+            })
+            .unwrap();
+    }
+}
+
+impl<K, V> Clone for SignalMapMut<K, V> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<K, V> Deref for SignalMapMut<K, V> {
+    type Target = SignalMap<K, V>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> Default for SignalMapMut<K, V> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(BTreeMap::new())
+    }
+}
+
+impl<K: Ord + Clone, V: Clone> From<BTreeMap<K, V>> for SignalMapMut<K, V> {
+    #[inline]
+    fn from(entries: BTreeMap<K, V>) -> Self {
+        Self::new(entries)
+    }
+}
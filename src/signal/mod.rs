@@ -1,21 +1,49 @@
+mod clock;
+mod computed;
+#[cfg(feature = "debug")]
+#[cfg_attr(docsrs, doc(cfg(feature = "debug")))]
+pub mod debug;
 mod error;
+mod map;
+mod pending;
 mod raw;
+mod scope;
+#[cfg(feature = "storage")]
+#[cfg_attr(docsrs, doc(cfg(feature = "storage")))]
+pub mod storage;
+mod store;
+mod stream;
+mod timer;
 mod unsub;
 mod value;
+mod vec;
 
+use core::cell::{Cell, RefCell};
 use core::future::Future;
 use core::ops::Deref;
 
 use alloc::rc::Rc;
+use futures::future::{self, Either};
 use futures::stream::{Stream, StreamExt};
 
 use crate::utils;
 
+use self::computed::Computed;
 use self::raw::RawSignal;
 
+pub use self::raw::{max_update_depth, set_max_update_depth};
+
+pub use clock::*;
 pub use error::*;
+pub use map::*;
+pub use pending::*;
+pub use scope::*;
+pub use store::*;
+pub use stream::*;
+pub use timer::*;
 pub use unsub::*;
 pub use value::*;
+pub use vec::*;
 
 #[repr(transparent)]
 pub struct Signal<T: 'static>(Rc<RawSignal<T>>);
@@ -32,11 +60,15 @@ impl<T> Signal<T> {
         B: Into<Option<T>>,
         F: Future<Output = T> + 'static,
     {
-        let raw = RawSignal::new(before.into());
-        let this = Self::new_from_raw(raw.shared());
+        let this = Self::new_from_raw(RawSignal::new(before.into()));
+        let weak = Rc::downgrade(this.raw());
         utils::spawn(async move {
-            raw.set(future.await);
+            let value = future.await;
+            if let Some(raw) = weak.upgrade() {
+                raw.set(value);
+            }
         });
+        Pending::register(&this);
         this
     }
 
@@ -46,14 +78,33 @@ impl<T> Signal<T> {
         I: Into<Option<T>>,
         S: Stream<Item = T> + 'static,
     {
-        let raw = RawSignal::new(init.into());
-        let this = Self::new_from_raw(raw.shared());
+        let this = Self::new_from_raw(RawSignal::new(init.into()));
+        let weak = Rc::downgrade(this.raw());
         utils::spawn(async move {
             let mut stream = core::pin::pin!(stream);
             while let Some(value) = stream.next().await {
-                raw.set(value);
+                match weak.upgrade() {
+                    Some(raw) => raw.set(value),
+                    _ => break,
+                }
             }
         });
+        Pending::register(&this);
+        this
+    }
+
+    /// Creates a signal holding the result of `compute`, evaluated again each time one of the
+    /// signals it reads changes.
+    ///
+    /// The signals read with [`Signal::try_get`] (or [`Signal::get`]) during an evaluation are
+    /// tracked, so that only the signals read during the latest evaluation are subscribed to.
+    #[inline]
+    pub fn computed<F>(compute: F) -> Self
+    where
+        F: FnMut() -> T + 'static,
+    {
+        let this = Self::new_from_raw(RawSignal::new(None));
+        this.push_dependency(Computed::new(&this, compute));
         this
     }
 
@@ -62,11 +113,22 @@ impl<T> Signal<T> {
         &self.0
     }
 
+    /// Names the signal, to tell it apart when inspecting the graph of signals with the `debug`
+    /// feature. Without this feature, this is a noop.
+    #[inline]
+    #[cfg_attr(not(feature = "debug"), allow(unused_variables))]
+    pub fn named(self, name: &str) -> Self {
+        #[cfg(feature = "debug")]
+        self.raw().node().set_name(name);
+        self
+    }
+
     #[inline]
     pub fn try_get(&self) -> Result<T, SignalGetError>
     where
         T: Clone,
     {
+        computed::track(self);
         self.raw().try_get()
     }
 
@@ -81,11 +143,14 @@ impl<T> Signal<T> {
     #[inline]
     fn compose<U, F>(&self, raw: RawSignal<U>, mut notify: F) -> Signal<U>
     where
-        F: FnMut(&RawSignal<U>, &T, &mut SignalUnsubscriber<T>) + 'static,
+        F: FnMut(&Rc<RawSignal<U>>, &T, &mut SignalUnsubscriber<T>) + 'static,
     {
         let signal = Signal::new_from_raw(raw);
         let weak = Rc::downgrade(signal.raw());
 
+        #[cfg(feature = "debug")]
+        signal.raw().node().add_parent(self.raw().node());
+
         self.for_each_inner(move |value, unsub| match weak.upgrade() {
             Some(raw) => notify(&raw, value, unsub),
             _ => unsub.unsubscribe(),
@@ -207,6 +272,127 @@ impl<T> Signal<T> {
         })
     }
 
+    /// Returns a [`Stream`] of the values taken by the signal, starting with the current one.
+    ///
+    /// The subscription to the signal is cancelled when the stream is dropped.
+    #[inline]
+    pub fn to_stream(&self) -> SignalStream<T>
+    where
+        T: Clone,
+    {
+        SignalStream::new(self, false)
+    }
+
+    /// Waits for the signal to take a new value, and returns it.
+    ///
+    /// The signal is subscribed to right away, so values set before the future is first polled
+    /// are not missed.
+    pub fn changed(&self) -> impl Future<Output = T> + 'static
+    where
+        T: Clone,
+    {
+        let signal = self.clone();
+        let mut stream = SignalStream::new(self, true);
+        async move {
+            // The stream can't end while the signal is alive.
+            let value = stream.next().await.unwrap();
+            drop(signal);
+            value
+        }
+    }
+
+    /// Waits for the signal to hold a value satisfying `predicate`, and returns it.
+    ///
+    /// If the current value of the signal already satisfies `predicate`, it is returned right away.
+    pub fn wait_for<P>(&self, mut predicate: P) -> impl Future<Output = T> + 'static
+    where
+        T: Clone,
+        P: FnMut(&T) -> bool + 'static,
+    {
+        match self.try_get() {
+            Ok(value) if predicate(&value) => Either::Left(future::ready(value)),
+            _ => Either::Right(self.filter(predicate).changed()),
+        }
+    }
+
+    /// Returns a signal that only takes the values of `self` after no new value was received for
+    /// `ms` milliseconds.
+    #[inline]
+    pub fn debounce(&self, ms: u32) -> Signal<T>
+    where
+        T: Clone,
+    {
+        self.debounce_with(BrowserClock, ms)
+    }
+
+    /// Same as [`Signal::debounce`], using the given [`Clock`].
+    pub fn debounce_with<C: Clock>(&self, clock: C, ms: u32) -> Signal<T>
+    where
+        T: Clone,
+    {
+        let mut timeout = None;
+        self.compose(RawSignal::new(None), move |raw, value, _| {
+            let weak = Rc::downgrade(raw);
+            let value = value.clone();
+            // Dropping the previous timeout cancels it.
+            timeout.replace(clock.set_timeout(ms, move || {
+                if let Some(raw) = weak.upgrade() {
+                    raw.set(value);
+                }
+            }));
+        })
+    }
+
+    /// Returns a signal that takes the values of `self` at most once every `ms` milliseconds.
+    ///
+    /// The values received in between are not lost: the latest one is taken at the end of the period.
+    #[inline]
+    pub fn throttle(&self, ms: u32) -> Signal<T>
+    where
+        T: Clone,
+    {
+        self.throttle_with(BrowserClock, ms)
+    }
+
+    /// Same as [`Signal::throttle`], using the given [`Clock`].
+    pub fn throttle_with<C: Clock>(&self, clock: C, ms: u32) -> Signal<T>
+    where
+        T: Clone,
+    {
+        let period = f64::from(ms);
+        let last = Rc::new(Cell::new(None::<f64>));
+        let latest = Rc::new(RefCell::new(None::<T>));
+        let mut trailing = None;
+
+        self.compose(RawSignal::new(None), move |raw, value, _| {
+            let now = clock.now();
+            match last.get() {
+                Some(time) if now - time < period => {
+                    if latest.replace(Some(value.clone())).is_none() {
+                        let (weak, last, latest) = (Rc::downgrade(raw), last.clone(), latest.clone());
+                        // Rounded up without f64::ceil, which needs std.
+                        let remaining = time + period - now;
+                        let delay = remaining as u32 + u32::from(f64::from(remaining as u32) < remaining);
+                        trailing.replace(clock.set_timeout(delay, move || {
+                            if let Some(value) = latest.take() {
+                                last.set(Some(time + period));
+                                if let Some(raw) = weak.upgrade() {
+                                    raw.set(value);
+                                }
+                            }
+                        }));
+                    }
+                }
+                _ => {
+                    trailing.take();
+                    latest.take();
+                    last.set(Some(now));
+                    raw.set(value.clone());
+                }
+            }
+        })
+    }
+
     #[inline]
     pub fn for_each<F>(&self, notify: F) -> SignalUnsubscriber<T>
     where
@@ -228,12 +414,24 @@ impl<T> Signal<T> {
         });
     }
 
+    /// Subscribes to the signal without a way to unsubscribe, unless a [`Scope`] is running, in
+    /// which case the subscription lasts as long as that scope.
     #[inline]
     pub fn for_each_forever<F>(&self, notify: F)
     where
         F: FnMut(&T) + 'static,
     {
-        self.raw().raw_for_each(|_| notify);
+        Scope::adopt(self.for_each(notify));
+    }
+
+    /// Adds a dependency to this signal.
+    ///
+    /// The dependency will be dropped at the same time as the last clone of the signal.
+    ///
+    /// If `D` does not need to be dropped, calling this method is reduced to a noop.
+    #[inline]
+    pub fn push_dependency<D: 'static>(&self, dep: D) {
+        self.raw().push_dependency(dep);
     }
 }
 
@@ -263,6 +461,18 @@ impl<T> SignalMut<T> {
         Self(Signal::new_from_raw(RawSignal::new(initial_value)))
     }
 
+    /// See [`Signal::named`].
+    #[inline]
+    pub fn named(self, name: &str) -> Self {
+        Self(self.0.named(name))
+    }
+
+    /// Sets the value of the signal and notifies its subscribers.
+    ///
+    /// When called by one of the subscribers of the signal, the value is queued and delivered
+    /// once all subscribers were notified of the current value. Fails with
+    /// [`SignalUpdatingError::InfiniteLoop`] if more than [`max_update_depth`] values get queued
+    /// this way during a single update.
     #[inline]
     pub fn try_set(&self, new_value: T) -> Result<(), SignalUpdatingError> {
         self.raw().try_set(new_value)
@@ -289,6 +499,28 @@ impl<T> SignalMut<T> {
         self.try_mutate(mutate).unwrap();
     }
 
+    /// Same as [`SignalMut::try_mutate`], except that when called by one of the subscribers of the
+    /// signal, the mutated value is queued like with [`SignalMut::try_set`] instead of failing.
+    ///
+    /// The latest value of the signal (queued or not) is cloned to be mutated.
+    #[inline]
+    pub fn try_update<F>(&self, update: F) -> Result<(), SignalUpdatingError>
+    where
+        T: Clone,
+        F: FnOnce(&mut T),
+    {
+        self.raw().try_update(update)
+    }
+
+    #[inline]
+    pub fn update<F>(&self, update: F)
+    where
+        T: Clone,
+        F: FnOnce(&mut T),
+    {
+        self.try_update(update).unwrap();
+    }
+
     #[inline]
     pub fn for_each<F>(&self, notify: F) -> SignalUnsubscriber<T>
     where
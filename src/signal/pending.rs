@@ -0,0 +1,105 @@
+use core::cell::{Cell, RefCell};
+
+use alloc::rc::Rc;
+
+use crate::utils::local;
+
+use super::{Signal, SignalMut};
+
+local! {
+    /// The innermost [`Pending`] tracker, if any.
+    static CURRENT: RefCell<Option<Pending>> = RefCell::new(None);
+}
+
+/// Counts the signals created with [`Signal::from_future`] or [`Signal::from_stream`] that have not
+/// yet received their first value.
+///
+/// Only the signals created inside of [`Pending::track`] are counted.
+#[derive(Clone)]
+pub struct Pending(SignalMut<usize>);
+
+impl Pending {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Runs `f`, counting all uninitialized asynchronous signals created during the call.
+    ///
+    /// Calls to this method can be nested, in which case signals are only counted by the innermost
+    /// tracker.
+    #[inline]
+    pub fn track<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let previous = CURRENT.with(|current| current.replace(Some(self.clone())));
+        let result = f();
+        CURRENT.with(|current| current.replace(previous));
+        result
+    }
+
+    /// The number of tracked signals that are still uninitialized.
+    #[inline]
+    pub fn count(&self) -> &Signal<usize> {
+        &self.0
+    }
+
+    #[inline]
+    pub fn is_pending(&self) -> bool {
+        self.0.get() != 0
+    }
+
+    /// Registers `signal` to the current tracker, if it is uninitialized.
+    pub(super) fn register<T>(signal: &Signal<T>) {
+        let Some(pending) = CURRENT.with(|current| current.borrow().clone()) else {
+            return;
+        };
+
+        if signal.raw().is_init() {
+            return;
+        }
+
+        pending.0.mutate(|count| *count += 1);
+        let registration = Rc::new(Registration {
+            count: pending.0,
+            done: Cell::new(false),
+        });
+
+        // The signal may be dropped before receiving its first value.
+        signal.push_dependency(registration.clone());
+        signal.for_each_inner(move |_, unsub| {
+            registration.done();
+            unsub.unsubscribe();
+        });
+    }
+}
+
+/// A signal counted by a [`Pending`] tracker, uncounted once it receives a value or is dropped.
+struct Registration {
+    count: SignalMut<usize>,
+    done: Cell<bool>,
+}
+
+impl Registration {
+    #[inline]
+    fn done(&self) {
+        if !self.done.replace(true) {
+            self.count.mutate(|count| *count -= 1);
+        }
+    }
+}
+
+impl Drop for Registration {
+    #[inline]
+    fn drop(&mut self) {
+        self.done();
+    }
+}
+
+impl Default for Pending {
+    #[inline]
+    fn default() -> Self {
+        Self(SignalMut::new(0))
+    }
+}
@@ -1,9 +1,13 @@
 //! A [`Broadcast`] is used to register subscribers (closures to be invoked on state change),
 //! and notify them, whilst allowing subscription/unsubscription at any time safely.
-
-// todo: erase typings of broadcast to save wasm binary size.
-
-use core::cell::{Cell, UnsafeCell};
+//!
+//! All the logic lives in the untyped [`RawBroadcast`], which passes values around as `*const ()`,
+//! so that it is compiled once instead of once per value type. [`Broadcast`] is only a thin typed
+//! wrapper around it.
+
+use core::cell::{Cell, RefCell};
+use core::marker::PhantomData;
+use core::mem;
 use core::ptr::NonNull;
 
 use alloc::boxed::Box;
@@ -11,18 +15,19 @@ use alloc::vec::Vec;
 
 use super::SubscriberId;
 
-/// A closure that reacts to a new value, passed by reference.
-type NotifyFn<T> = dyn FnMut(&T);
+/// A closure that reacts to a new value, passed by an erased pointer.
+type RawNotifyFn = dyn FnMut(*const ());
 
 /// A subscriber to a signal, with it's ID, notify closure
 /// and wether it is still active or is awaiting being dropped.
-struct Subscriber<T> {
+struct Subscriber {
     id: SubscriberId,
     active: Cell<bool>,
-    notify: NonNull<NotifyFn<T>>,
+    /// Owned, but not boxed so that the closure can be called without borrowing the subscriber list.
+    notify: NonNull<RawNotifyFn>,
 }
 
-impl<T> Subscriber<T> {
+impl Subscriber {
     /// Returns the ID of this subscriber.
     #[inline]
     fn id(&self) -> SubscriberId {
@@ -36,20 +41,14 @@ impl<T> Subscriber<T> {
     fn active(&self) -> bool {
         self.active.get()
     }
-
-    /// Returns a pointer to the notify function of this
-    /// subscriber.
-    #[inline]
-    fn notify(&self) -> NonNull<NotifyFn<T>> {
-        self.notify
-    }
 }
 
-impl<T> Drop for Subscriber<T> {
+impl Drop for Subscriber {
     #[inline]
     fn drop(&mut self) {
+        // SAFETY: notify was created with Box::into_raw, and subscribers are never dropped while notified.
         unsafe {
-            _ = Box::from_raw(self.notify.as_mut());
+            _ = Box::from_raw(self.notify.as_ptr());
         }
     }
 }
@@ -62,101 +61,167 @@ enum State {
     Subscribing,
 }
 
-/// A [`Broadcast`] is used to register subscribers (closures to be invoked on state change),
-/// and notify them, whilst allowing safe subscription/unsubscription at any time.
-pub struct Broadcast<T> {
+/// The untyped implementation of [`Broadcast`].
+struct RawBroadcast {
     state: Cell<State>,
     next_id: Cell<usize>,
     needs_retain: Cell<bool>,
-    subscribers: UnsafeCell<Vec<Subscriber<T>>>,
+    subscribers: RefCell<Vec<Subscriber>>,
 }
 
-impl<T> Broadcast<T> {
-    /// Retains the subscribers that still want to be notified, the other
-    /// being dropped.
+impl RawBroadcast {
+    /// Retains the subscribers that still want to be notified, the other being dropped.
     ///
-    /// # Safety
-    ///
-    /// The state of the broadcast must be `State::Idling`, so that
-    /// no subscribers is borrowed while ratain takes place.
-    unsafe fn retain(&self) {
+    /// Must only be called while idling, so that no subscriber is being called.
+    fn retain(&self) {
         if self.needs_retain.replace(false) {
-            let subscribers = self.subscribers.get();
-            (*subscribers).retain(Subscriber::active)
+            let mut subscribers = self.subscribers.borrow_mut();
+            let (active, inactive): (Vec<_>, Vec<_>) =
+                mem::take(&mut *subscribers).into_iter().partition(Subscriber::active);
+            *subscribers = active;
+            // Dropped subscribers may unsubscribe from this broadcast.
+            drop(subscribers);
+            drop(inactive);
         }
     }
 
-    /// Returns the next ID to be attributed to a subscriber.
-    pub fn next_id(&self) -> SubscriberId {
+    fn next_id(&self) -> SubscriberId {
         let id = self.next_id.get();
         self.next_id.set(id + 1);
         SubscriberId(id)
     }
 
-    /// Push a new subscriber at the end of the subscriber list.
-    ///
-    /// The `id` given must be the last one in the list (e.g. provided by the latest call
-    /// to [`Broadcast::next_id`]). Else, expect funny (but safe) stuff to happen.
+    /// Returns the notify closure of the `index`-th subscriber, if it exists and is active.
+    fn get_notify(&self, index: usize) -> Option<Option<NonNull<RawNotifyFn>>> {
+        let subscribers = self.subscribers.borrow();
+        let subscriber = subscribers.get(index)?;
+        Some(subscriber.active().then_some(subscriber.notify))
+    }
+
+    /// # Safety
     ///
-    /// if `Some` `value` is provided, the subscriber is immediately
-    /// notified (given the broadcast is not already notifying).
-    pub fn push_subscriber(&self, id: SubscriberId, notify: Box<NotifyFn<T>>, value: Option<&T>) {
-        let subscriber = Subscriber {
+    /// `notify` must be a pointer obtained with `Box::into_raw`, and `value` must point to a value
+    /// of the type expected by `notify`.
+    unsafe fn push_subscriber(&self, id: SubscriberId, notify: NonNull<RawNotifyFn>, value: Option<*const ()>) {
+        self.subscribers.borrow_mut().push(Subscriber {
             id,
             active: Cell::new(true),
-            notify: NonNull::new(Box::into_raw(notify)).unwrap(),
-        };
-
-        let mut notify = subscriber.notify();
-        let subscribers = self.subscribers.get();
-
-        unsafe {
-            (*subscribers).push(subscriber);
-
-            if let Some(value) = value {
-                match self.state.get() {
-                    State::Idling => {
-                        self.state.set(State::Subscribing);
-                        notify.as_mut()(value);
-                        self.state.set(State::Idling);
-                    }
-                    State::Notifying => (),
-                    State::Subscribing => {
-                        notify.as_mut()(value);
-                    }
+            notify,
+        });
+
+        if let Some(value) = value {
+            match self.state.get() {
+                State::Idling => {
+                    self.state.set(State::Subscribing);
+                    (&mut *notify.as_ptr())(value);
+                    self.state.set(State::Idling);
+                }
+                State::Notifying => (),
+                State::Subscribing => {
+                    (&mut *notify.as_ptr())(value);
                 }
             }
         }
     }
 
-    /// Notify all subscribers of a `value` change.
+    /// # Safety
     ///
-    /// If the state was already notifying (all subscribers or just a single new one), this
-    /// function does nothing.
-    pub fn notify(&self, value: &T) {
+    /// `value` must point to a value of the type expected by the subscribers.
+    unsafe fn notify(&self, value: *const ()) {
         if self.state.get() != State::Idling {
             return;
         }
 
         self.state.set(State::Notifying);
-        let subscribers = self.subscribers.get();
 
-        unsafe {
-            let mut i = 0;
+        // Subscribers may be pushed while notifying, they are notified too.
+        let mut i = 0;
+        while let Some(notify) = self.get_notify(i) {
+            if let Some(notify) = notify {
+                // Inactive subscribers are only dropped once idling, so notify is still alive.
+                (&mut *notify.as_ptr())(value);
+            }
+            i += 1;
+        }
 
-            while i < (*subscribers).len() {
-                let subscriber = (*subscribers).as_mut_ptr().add(i);
-                if (*subscriber).active() {
-                    let mut notify = (*subscriber).notify();
-                    notify.as_mut()(value);
-                }
-                i += 1;
+        self.state.set(State::Idling);
+        self.retain();
+    }
+
+    fn unsubscribe(&self, id: SubscriberId) -> bool {
+        let mut subscribers = self.subscribers.borrow_mut();
+
+        let Ok(index) = subscribers.binary_search_by_key(&id, Subscriber::id) else {
+            return false;
+        };
+
+        match self.state.get() {
+            State::Idling => {
+                let subscriber = subscribers.remove(index);
+                drop(subscribers);
+                subscriber.active()
+            }
+            _ => {
+                self.needs_retain.set(true);
+                subscribers[index].active.replace(false)
             }
+        }
+    }
+}
 
-            self.retain()
+impl Default for RawBroadcast {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            state: Cell::new(State::Idling),
+            next_id: Cell::new(0),
+            needs_retain: Cell::new(false),
+            subscribers: RefCell::new(Vec::new()),
         }
+    }
+}
 
-        self.state.set(State::Idling);
+/// A [`Broadcast`] is used to register subscribers (closures to be invoked on state change),
+/// and notify them, whilst allowing safe subscription/unsubscription at any time.
+pub struct Broadcast<T> {
+    raw: RawBroadcast,
+    _marker: PhantomData<fn(&T)>,
+}
+
+impl<T> Broadcast<T> {
+    /// Returns the next ID to be attributed to a subscriber.
+    #[inline]
+    pub fn next_id(&self) -> SubscriberId {
+        self.raw.next_id()
+    }
+
+    /// Push a new subscriber at the end of the subscriber list.
+    ///
+    /// The `id` given must be the last one in the list (e.g. provided by the latest call
+    /// to [`Broadcast::next_id`]). Else, expect funny (but safe) stuff to happen.
+    ///
+    /// if `Some` `value` is provided, the subscriber is immediately
+    /// notified (given the broadcast is not already notifying).
+    #[inline]
+    pub fn push_subscriber<F>(&self, id: SubscriberId, mut notify: F, value: Option<&T>)
+    where
+        F: FnMut(&T) + 'static,
+    {
+        // SAFETY: the broadcast only ever passes pointers to T to its subscribers.
+        let notify: Box<RawNotifyFn> = Box::new(move |value| notify(unsafe { &*value.cast::<T>() }));
+        let notify = NonNull::new(Box::into_raw(notify)).unwrap();
+        let value = value.map(|value| value as *const T as *const ());
+        unsafe { self.raw.push_subscriber(id, notify, value) }
+    }
+
+    /// Notify all subscribers of a `value` change.
+    ///
+    /// If the state was already notifying (all subscribers or just a single new one), this
+    /// function does nothing.
+    #[inline]
+    pub fn notify(&self, value: &T) {
+        // SAFETY: value is a T.
+        unsafe { self.raw.notify(value as *const T as *const ()) }
     }
 
     /// Unsubscribes the subscriber with the given `id`.
@@ -164,23 +229,11 @@ impl<T> Broadcast<T> {
     /// If the subscriber is already unsubscribed, this function does nothing.
     ///
     /// The subscriber might not get dropped right away, but won't be called again.
-    pub fn unsubscribe(&self, id: SubscriberId) {
-        let subscribers = self.subscribers.get();
-
-        unsafe {
-            if let Ok(index) = (*subscribers).binary_search_by_key(&id, Subscriber::id) {
-                match self.state.get() {
-                    State::Idling => {
-                        (*subscribers).remove(index);
-                    }
-                    _ => {
-                        let subscriber = &(*subscribers)[index];
-                        subscriber.active.set(false);
-                        self.needs_retain.set(true);
-                    }
-                }
-            }
-        }
+    ///
+    /// Returns true iff the subscriber was still subscribed.
+    #[inline]
+    pub fn unsubscribe(&self, id: SubscriberId) -> bool {
+        self.raw.unsubscribe(id)
     }
 }
 
@@ -188,10 +241,8 @@ impl<T> Default for Broadcast<T> {
     #[inline]
     fn default() -> Self {
         Self {
-            state: Cell::new(State::Idling),
-            next_id: Cell::new(0),
-            needs_retain: Cell::new(false),
-            subscribers: UnsafeCell::new(Vec::new()),
+            raw: RawBroadcast::default(),
+            _marker: PhantomData,
         }
     }
 }
@@ -0,0 +1,71 @@
+use core::cell::RefCell;
+
+use super::super::{SignalGetError, SignalUpdatingError};
+use super::broadcast::Broadcast;
+use super::SubscriberId;
+
+/// A collection `C` whose changes are broadcasted as diffs `D`.
+pub struct RawCollection<C, D> {
+    broadcast: Broadcast<D>,
+    data: RefCell<C>,
+}
+
+impl<C, D> RawCollection<C, D> {
+    #[inline]
+    pub fn new(data: C) -> Self {
+        Self {
+            broadcast: Broadcast::default(),
+            data: RefCell::new(data),
+        }
+    }
+
+    /// Subscribes to the diffs of the collection.
+    ///
+    /// The new subscriber is immediately notified of the diff returned by `snapshot`,
+    /// describing the current state of the collection.
+    #[inline]
+    pub fn raw_for_each<F, G, S>(&self, make_notify: G, snapshot: S) -> SubscriberId
+    where
+        F: FnMut(&D) + 'static,
+        G: FnOnce(SubscriberId) -> F,
+        S: FnOnce(&C) -> D,
+    {
+        let id = self.broadcast.next_id();
+        let notify = make_notify(id);
+        let data = self.data.try_borrow().ok();
+        let diff = data.as_deref().map(snapshot);
+        self.broadcast.push_subscriber(id, notify, diff.as_ref());
+        id
+    }
+
+    /// Updates the collection, then notifies all subscribers of the returned diff, if any.
+    #[inline]
+    pub fn try_update<F>(&self, update: F) -> Result<(), SignalUpdatingError>
+    where
+        F: FnOnce(&mut C) -> Option<D>,
+    {
+        let mut data = self.data.try_borrow_mut().map_err(|_| SignalUpdatingError::Updating)?;
+        let diff = update(&mut data);
+        drop(data);
+        if let Some(diff) = diff {
+            // Prevents updates while notifying, which would otherwise be missed.
+            let _data = self.data.borrow();
+            self.broadcast.notify(&diff);
+        }
+        Ok(())
+    }
+
+    #[inline]
+    pub fn try_with<F, R>(&self, f: F) -> Result<R, SignalGetError>
+    where
+        F: FnOnce(&C) -> R,
+    {
+        let data = self.data.try_borrow().map_err(|_| SignalGetError::Updating)?;
+        Ok(f(&data))
+    }
+
+    #[inline]
+    pub fn unsubscribe(&self, id: SubscriberId) {
+        self.broadcast.unsubscribe(id);
+    }
+}
@@ -1,16 +1,44 @@
 mod broadcast;
+mod collection;
 
-use core::cell::RefCell;
+use core::any::Any;
+use core::cell::{Cell, RefCell};
+use core::mem;
 
 use alloc::boxed::Box;
+use alloc::collections::VecDeque;
 use alloc::rc::Rc;
+use alloc::vec::Vec;
 
+use crate::utils::local;
+
+#[cfg(feature = "debug")]
+use super::debug::Node;
 use super::{SignalGetError, SignalUpdatingError};
 
 use self::broadcast::Broadcast;
 
+pub use self::collection::RawCollection;
+
 type Data<T> = Rc<RefCell<Option<T>>>;
 
+local! {
+    static MAX_UPDATE_DEPTH: Cell<usize> = Cell::new(100);
+}
+
+/// Returns the maximum number of values that can be set to a signal while it is notifying its
+/// subscribers, before the update is considered an infinite loop.
+#[inline]
+pub fn max_update_depth() -> usize {
+    MAX_UPDATE_DEPTH.with(Cell::get)
+}
+
+/// Sets the [maximum update depth](max_update_depth), 100 by default.
+#[inline]
+pub fn set_max_update_depth(depth: usize) {
+    MAX_UPDATE_DEPTH.with(|max| max.set(depth));
+}
+
 /// The ID of a subscription to a signal, can be used to unsubscribe from
 /// this signal.
 #[repr(transparent)]
@@ -20,6 +48,12 @@ pub struct SubscriberId(usize);
 pub struct RawSignal<T> {
     broadcast: Broadcast<T>,
     data: Data<T>,
+    deps: RefCell<Vec<Box<dyn Any>>>,
+    notifying: Cell<bool>,
+    /// The values set while notifying, delivered once the current notify pass is over.
+    queue: RefCell<VecDeque<T>>,
+    #[cfg(feature = "debug")]
+    node: Node,
 }
 
 impl<T> RawSignal<T> {
@@ -28,6 +62,11 @@ impl<T> RawSignal<T> {
         Self {
             broadcast: Broadcast::default(),
             data: Rc::new(RefCell::new(value)),
+            deps: RefCell::default(),
+            notifying: Cell::new(false),
+            queue: RefCell::default(),
+            #[cfg(feature = "debug")]
+            node: Node::new::<T>(),
         }
     }
 
@@ -36,6 +75,11 @@ impl<T> RawSignal<T> {
         Self {
             broadcast: Broadcast::default(),
             data: self.data.clone(),
+            deps: RefCell::default(),
+            notifying: Cell::new(false),
+            queue: RefCell::default(),
+            #[cfg(feature = "debug")]
+            node: Node::new::<T>(),
         }
     }
 
@@ -46,17 +90,68 @@ impl<T> RawSignal<T> {
         G: FnOnce(SubscriberId) -> F,
     {
         let id = self.broadcast.next_id();
-        let notify = Box::new(make_notify(id));
-        let data = self.data.try_borrow().ok();
-        let value = data.as_ref().and_then(|value| value.as_ref());
-        self.broadcast.push_subscriber(id, notify, value);
+        let notify = make_notify(id);
+        #[cfg(feature = "debug")]
+        self.node.add_subscriber();
+        self.notifying(|| {
+            let data = self.data.try_borrow().ok();
+            let value = data.as_ref().and_then(|value| value.as_ref());
+            self.broadcast.push_subscriber(id, notify, value);
+        })
+        .unwrap();
         id
     }
 
+    #[inline]
+    pub fn push_dependency<D: 'static>(&self, dep: D) {
+        if mem::needs_drop::<D>() {
+            self.deps.borrow_mut().push(Box::new(dep));
+        }
+    }
+
+    /// Runs `notify`, then delivers the values that were set in the meantime, in order.
+    ///
+    /// If the signal is already notifying, the values are delivered by the outermost call.
+    fn notifying<F: FnOnce()>(&self, notify: F) -> Result<(), SignalUpdatingError> {
+        if self.notifying.replace(true) {
+            notify();
+            return Ok(());
+        }
+
+        notify();
+
+        let mut depth = 0;
+        let result = loop {
+            let Some(value) = self.queue.borrow_mut().pop_front() else {
+                break Ok(());
+            };
+
+            depth += 1;
+            if depth > max_update_depth() {
+                drop(mem::take(&mut *self.queue.borrow_mut()));
+                break Err(SignalUpdatingError::InfiniteLoop);
+            }
+
+            *self.data.borrow_mut() = Some(value);
+            let data = self.data.borrow();
+            self.broadcast.notify(data.as_ref().unwrap());
+        };
+
+        self.notifying.set(false);
+        result
+    }
+
+    #[inline]
+    pub fn try_notify_all(&self) -> Result<(), SignalUpdatingError> {
+        self.notifying(|| {
+            let data = self.data.borrow();
+            self.broadcast.notify(data.as_ref().unwrap());
+        })
+    }
+
     #[inline]
     pub fn notify_all(&self) {
-        let data = self.data.borrow();
-        self.broadcast.notify(data.as_ref().unwrap());
+        self.try_notify_all().unwrap()
     }
 
     #[inline]
@@ -64,30 +159,94 @@ impl<T> RawSignal<T> {
         self.try_set(new_value).unwrap()
     }
 
+    /// Sets the value of the signal and notifies its subscribers.
+    ///
+    /// If the signal is notifying its subscribers, the value is queued and delivered after the
+    /// current notify pass.
     #[inline]
     pub fn try_set(&self, new_value: T) -> Result<(), SignalUpdatingError> {
-        let mut data = self.data.try_borrow_mut().map_err(|_| SignalUpdatingError)?;
+        if self.notifying.get() {
+            self.queue.borrow_mut().push_back(new_value);
+            return Ok(());
+        }
+
+        let mut data = self.data.try_borrow_mut().map_err(|_| SignalUpdatingError::Updating)?;
         *data = Some(new_value);
         drop(data);
-        self.notify_all();
-        Ok(())
+        self.try_notify_all()
+    }
+
+    /// Sets the value of the signal to `new_value`, unless they are equal.
+    #[inline]
+    pub fn set_if_changed(&self, new_value: &T)
+    where
+        T: Clone + PartialEq,
+    {
+        let changed = self
+            .data
+            .try_borrow()
+            .map_or(true, |data| data.as_ref() != Some(new_value));
+        if changed {
+            self.set(new_value.clone());
+        }
     }
 
+    /// Unlike [`RawSignal::try_set`], fails if the signal is notifying its subscribers.
     #[inline]
     pub fn try_mutate<F>(&self, mutate: F) -> Result<(), SignalUpdatingError>
     where
         F: FnOnce(&mut T),
     {
-        let mut data = self.data.try_borrow_mut().map_err(|_| SignalUpdatingError)?;
-        mutate(data.as_mut().ok_or(SignalUpdatingError)?);
+        let mut data = self.data.try_borrow_mut().map_err(|_| SignalUpdatingError::Updating)?;
+        mutate(data.as_mut().ok_or(SignalUpdatingError::Updating)?);
         drop(data);
-        self.notify_all();
-        Ok(())
+        self.try_notify_all()
+    }
+
+    /// Same as [`RawSignal::try_mutate`], except that if the signal is notifying its subscribers, the
+    /// latest value is cloned, mutated and queued like with [`RawSignal::try_set`].
+    pub fn try_update<F>(&self, update: F) -> Result<(), SignalUpdatingError>
+    where
+        T: Clone,
+        F: FnOnce(&mut T),
+    {
+        if !self.notifying.get() {
+            return self.try_mutate(update);
+        }
+
+        // Mutates the latest queued value, so that none of the queued updates is lost.
+        let queued = self.queue.borrow().back().cloned();
+        let mut value = match queued {
+            Some(value) => value,
+            _ => self
+                .data
+                .try_borrow()
+                .ok()
+                .and_then(|data| data.clone())
+                .ok_or(SignalUpdatingError::Updating)?,
+        };
+        update(&mut value);
+        self.try_set(value)
     }
 
     #[inline]
     pub fn unsubscribe(&self, id: SubscriberId) {
-        self.broadcast.unsubscribe(id);
+        if self.broadcast.unsubscribe(id) {
+            #[cfg(feature = "debug")]
+            self.node.remove_subscriber();
+        }
+    }
+
+    #[cfg(feature = "debug")]
+    #[inline]
+    pub fn node(&self) -> &Node {
+        &self.node
+    }
+
+    /// Returns true iff the signal holds a value (or is currently being updated).
+    #[inline]
+    pub fn is_init(&self) -> bool {
+        self.data.try_borrow().map_or(true, |data| data.is_some())
     }
 
     #[inline]
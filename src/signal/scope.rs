@@ -0,0 +1,89 @@
+use core::cell::RefCell;
+use core::fmt;
+
+use alloc::boxed::Box;
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+
+use crate::utils::local;
+
+use super::Unsubscribe;
+
+local! {
+    /// The innermost running [`Scope`], if any.
+    static CURRENT: RefCell<Option<Scope>> = RefCell::new(None);
+}
+
+#[derive(Default)]
+struct ScopeInner {
+    subscriptions: RefCell<Vec<Box<dyn Unsubscribe>>>,
+}
+
+impl Drop for ScopeInner {
+    #[inline]
+    fn drop(&mut self) {
+        for mut unsub in self.subscriptions.take() {
+            unsub.unsubscribe();
+        }
+    }
+}
+
+/// Owns subscriptions to signals, and releases them once the last clone of the scope is dropped.
+///
+/// Subscriptions made with `for_each_forever` while a scope is [running](Scope::run) are
+/// registered to that scope, instead of lasting forever. Each [`Component`](crate::component::Component)
+/// has its own scope, which runs while its attributes and views are initialized.
+#[derive(Clone, Default)]
+pub struct Scope(Rc<ScopeInner>);
+
+impl Scope {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the innermost running scope, if any.
+    #[inline]
+    pub fn current() -> Option<Scope> {
+        CURRENT.with(|current| current.borrow().clone())
+    }
+
+    /// Runs `f` with `self` as the current scope.
+    ///
+    /// Calls to this method can be nested, in which case subscriptions are only registered to the
+    /// innermost scope.
+    #[inline]
+    pub fn run<F, R>(&self, f: F) -> R
+    where
+        F: FnOnce() -> R,
+    {
+        let previous = CURRENT.with(|current| current.replace(Some(self.clone())));
+        let result = f();
+        CURRENT.with(|current| current.replace(previous));
+        result
+    }
+
+    /// Registers `unsub` to this scope, it will be unsubscribed when the scope is dropped.
+    #[inline]
+    pub fn push<U: Unsubscribe>(&self, unsub: U) {
+        if unsub.has_effect() {
+            self.0.subscriptions.borrow_mut().push(Box::new(unsub));
+        }
+    }
+
+    /// Registers `unsub` to the current scope, if any. Else, the subscription lasts forever.
+    #[inline]
+    pub(crate) fn adopt<U: Unsubscribe>(unsub: U) {
+        if let Some(scope) = Self::current() {
+            scope.push(unsub);
+        }
+    }
+}
+
+impl fmt::Debug for Scope {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scope")
+            .field("subscriptions", &self.0.subscriptions.borrow().len())
+            .finish()
+    }
+}
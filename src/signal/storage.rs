@@ -0,0 +1,127 @@
+//! Signals persisted in the [Web Storage](https://developer.mozilla.org/en-US/docs/Web/API/Web_Storage_API)
+//! of the browser, surviving reloads.
+
+use core::mem;
+
+use alloc::rc::Rc;
+use alloc::string::ToString;
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use web_sys::wasm_bindgen::closure::Closure;
+use web_sys::wasm_bindgen::JsCast;
+use web_sys::{Storage, StorageEvent};
+
+use super::SignalMut;
+
+/// The storage area of a persisted signal.
+#[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Default, Debug)]
+pub enum StorageKind {
+    /// [`localStorage`](https://developer.mozilla.org/en-US/docs/Web/API/Window/localStorage),
+    /// shared by all tabs and kept across browser sessions.
+    #[default]
+    Local,
+    /// [`sessionStorage`](https://developer.mozilla.org/en-US/docs/Web/API/Window/sessionStorage),
+    /// cleared when the tab is closed.
+    Session,
+}
+
+impl StorageKind {
+    #[inline]
+    fn storage(self) -> Option<Storage> {
+        let window = web_sys::window()?;
+        let storage = match self {
+            Self::Local => window.local_storage(),
+            Self::Session => window.session_storage(),
+        };
+        storage.ok().flatten()
+    }
+}
+
+/// Removes the `storage` event listener when dropped.
+struct StorageListener {
+    closure: Closure<dyn FnMut(StorageEvent)>,
+}
+
+impl Drop for StorageListener {
+    #[inline]
+    fn drop(&mut self) {
+        web_sys::window()
+            .unwrap()
+            .remove_event_listener_with_callback("storage", self.closure.as_ref().unchecked_ref())
+            .ok();
+    }
+}
+
+/// Same as [`persisted_in`], using [`StorageKind::Local`].
+#[inline]
+pub fn persisted<T>(key: &str, default: T) -> SignalMut<T>
+where
+    T: Serialize + DeserializeOwned + Clone + 'static,
+{
+    persisted_in(StorageKind::Local, key, default)
+}
+
+/// Returns a signal holding the value stored as JSON at `key` in the given storage, or `default` if
+/// there is none or it can't be deserialized.
+///
+/// Every change to the signal is written back to the storage. Changes made to the storage by other
+/// tabs are received through the [`storage`](https://developer.mozilla.org/en-US/docs/Web/API/Window/storage_event)
+/// event, whose listener is removed once the last clone of the signal is dropped.
+///
+/// If the storage is not available, the signal is not persisted.
+pub fn persisted_in<T>(kind: StorageKind, key: &str, default: T) -> SignalMut<T>
+where
+    T: Serialize + DeserializeOwned + Clone + 'static,
+{
+    let Some(storage) = kind.storage() else {
+        return SignalMut::new(default);
+    };
+
+    let load = |json: &str| serde_json::from_str::<T>(json).ok();
+    let stored = storage.get_item(key).ok().flatten();
+    let signal = SignalMut::new(stored.as_deref().and_then(load).unwrap_or_else(|| default.clone()));
+
+    // Write back every change, except the initial value.
+    let key = key.to_string();
+    let (area, area_key) = (storage.clone(), key.clone());
+    let mut initial = true;
+    signal.for_each_inner(move |value, _| {
+        if !mem::take(&mut initial) {
+            if let Ok(json) = serde_json::to_string(value) {
+                area.set_item(&area_key, &json).ok();
+            }
+        }
+    });
+
+    // Sync with the other tabs.
+    let weak = Rc::downgrade(signal.raw());
+    let closure = Closure::<dyn FnMut(StorageEvent)>::new(move |event: StorageEvent| {
+        if event.storage_area().as_ref() != Some(&storage) {
+            return;
+        }
+
+        // There is no key when the whole storage was cleared.
+        let value = match event.key() {
+            Some(other) if other != key => return,
+            Some(_) => match event.new_value() {
+                Some(json) => match load(&json) {
+                    Some(value) => value,
+                    _ => return,
+                },
+                _ => default.clone(),
+            },
+            _ => default.clone(),
+        };
+
+        if let Some(raw) = weak.upgrade() {
+            raw.set(value);
+        }
+    });
+
+    web_sys::window()
+        .unwrap()
+        .add_event_listener_with_callback("storage", closure.as_ref().unchecked_ref())
+        .unwrap();
+    signal.push_dependency(StorageListener { closure });
+    signal
+}
@@ -0,0 +1,160 @@
+use core::ops::Deref;
+
+use alloc::rc::Rc;
+
+use super::raw::RawSignal;
+use super::{Signal, SignalMut, SignalUpdatingError};
+
+type Getter<T, U> = Rc<dyn Fn(&T) -> &U>;
+
+type GetterMut<T, U> = Rc<dyn Fn(&mut T) -> &mut U>;
+
+/// A single piece of state, from which [`Lens`]es focusing on its parts can be derived.
+#[repr(transparent)]
+pub struct Store<T: 'static>(SignalMut<T>);
+
+impl<T> Store<T> {
+    #[inline]
+    pub fn new(initial_value: T) -> Self {
+        Self(SignalMut::new(initial_value))
+    }
+
+    /// Returns a lens focusing on the part of the state given by `get` and `get_mut`.
+    ///
+    /// The subscribers of the lens are only notified when that part of the state changes.
+    #[inline]
+    pub fn lens<U, G, M>(&self, get: G, get_mut: M) -> Lens<T, U>
+    where
+        U: Clone + PartialEq,
+        G: Fn(&T) -> &U + 'static,
+        M: Fn(&mut T) -> &mut U + 'static,
+    {
+        Lens::new(self.0.clone(), Rc::new(get), Rc::new(get_mut))
+    }
+}
+
+impl<T> Clone for Store<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Deref for Store<T> {
+    type Target = SignalMut<T>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> From<T> for Store<T> {
+    #[inline]
+    fn from(initial_value: T) -> Self {
+        Self::new(initial_value)
+    }
+}
+
+/// A part of the state of a [`Store`], that can be read and written like a [`SignalMut`].
+///
+/// Writing to a lens updates the whole store.
+pub struct Lens<T: 'static, U: 'static> {
+    store: SignalMut<T>,
+    get: Getter<T, U>,
+    get_mut: GetterMut<T, U>,
+    signal: Signal<U>,
+}
+
+impl<T, U> Lens<T, U> {
+    #[inline]
+    fn new(store: SignalMut<T>, get: Getter<T, U>, get_mut: GetterMut<T, U>) -> Self
+    where
+        U: Clone + PartialEq,
+    {
+        let getter = get.clone();
+        let signal = store.compose(RawSignal::new(None), move |raw, state, _| {
+            raw.set_if_changed(getter(state));
+        });
+
+        Self {
+            store,
+            get,
+            get_mut,
+            signal,
+        }
+    }
+
+    /// Returns a lens focusing on a part of the part of the state this lens focuses on.
+    #[inline]
+    pub fn lens<V, G, M>(&self, get: G, get_mut: M) -> Lens<T, V>
+    where
+        V: Clone + PartialEq,
+        G: Fn(&U) -> &V + 'static,
+        M: Fn(&mut U) -> &mut V + 'static,
+    {
+        let (outer, outer_mut) = (self.get.clone(), self.get_mut.clone());
+        Lens::new(
+            self.store.clone(),
+            Rc::new(move |state| get(outer(state))),
+            Rc::new(move |state| get_mut(outer_mut(state))),
+        )
+    }
+
+    /// Sets the part of the state this lens focuses on.
+    ///
+    /// Like [`SignalMut::try_set`], the new state is queued when called by one of the subscribers of the
+    /// store, see [`SignalMut::try_update`].
+    #[inline]
+    pub fn try_set(&self, new_value: U) -> Result<(), SignalUpdatingError>
+    where
+        T: Clone,
+    {
+        self.store.try_update(|state| *(self.get_mut)(state) = new_value)
+    }
+
+    #[inline]
+    pub fn set(&self, new_value: U)
+    where
+        T: Clone,
+    {
+        self.try_set(new_value).unwrap();
+    }
+
+    #[inline]
+    pub fn try_mutate<F>(&self, mutate: F) -> Result<(), SignalUpdatingError>
+    where
+        F: FnOnce(&mut U),
+    {
+        self.store.try_mutate(|state| mutate((self.get_mut)(state)))
+    }
+
+    #[inline]
+    pub fn mutate<F>(&self, mutate: F)
+    where
+        F: FnOnce(&mut U),
+    {
+        self.try_mutate(mutate).unwrap();
+    }
+}
+
+impl<T, U> Clone for Lens<T, U> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self {
+            store: self.store.clone(),
+            get: self.get.clone(),
+            get_mut: self.get_mut.clone(),
+            signal: self.signal.clone(),
+        }
+    }
+}
+
+impl<T, U> Deref for Lens<T, U> {
+    type Target = Signal<U>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.signal
+    }
+}
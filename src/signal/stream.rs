@@ -0,0 +1,89 @@
+use core::cell::RefCell;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+use alloc::rc::Rc;
+use futures::stream::Stream;
+
+use super::{DropUnsubscriber, Signal, SignalUnsubscriber};
+
+struct Shared<T> {
+    value: Option<T>,
+    waker: Option<Waker>,
+    closed: bool,
+}
+
+impl<T> Shared<T> {
+    /// Wakes the task waiting on the stream, if any.
+    #[inline]
+    fn wake(this: &RefCell<Self>) {
+        let waker = this.borrow_mut().waker.take();
+        if let Some(waker) = waker {
+            waker.wake();
+        }
+    }
+}
+
+/// Lives inside the subscriber, closes the stream when the signal drops it.
+struct Sender<T>(Rc<RefCell<Shared<T>>>);
+
+impl<T> Drop for Sender<T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.borrow_mut().closed = true;
+        Shared::wake(&self.0);
+    }
+}
+
+/// A [`Stream`] of the values taken by a [`Signal`], see [`Signal::to_stream`].
+///
+/// Only the latest value is kept: the values taken by the signal between two polls of the
+/// stream are skipped. The stream ends when the signal is dropped.
+#[must_use = "streams do nothing unless polled"]
+pub struct SignalStream<T: 'static> {
+    shared: Rc<RefCell<Shared<T>>>,
+    _unsub: DropUnsubscriber<SignalUnsubscriber<T>>,
+}
+
+impl<T: Clone> SignalStream<T> {
+    /// Subscribes to `signal`, ignoring its current value if `skip_current` is true.
+    pub(super) fn new(signal: &Signal<T>, skip_current: bool) -> Self {
+        let shared = Rc::new(RefCell::new(Shared {
+            value: None,
+            waker: None,
+            closed: false,
+        }));
+
+        let sender = Sender(shared.clone());
+        let mut skip = skip_current && signal.raw().is_init();
+        let unsub = signal.for_each(move |value| {
+            if skip {
+                skip = false;
+                return;
+            }
+            sender.0.borrow_mut().value = Some(value.clone());
+            Shared::wake(&sender.0);
+        });
+
+        Self {
+            shared,
+            _unsub: unsub.droppable(),
+        }
+    }
+}
+
+impl<T> Stream for SignalStream<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut shared = self.shared.borrow_mut();
+        match shared.value.take() {
+            Some(value) => Poll::Ready(Some(value)),
+            _ if shared.closed => Poll::Ready(None),
+            _ => {
+                shared.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
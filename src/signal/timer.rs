@@ -0,0 +1,123 @@
+//! Signals driven by the browser's timers.
+
+use core::cell::{Cell, RefCell};
+
+use alloc::rc::Rc;
+use web_sys::wasm_bindgen::closure::Closure;
+use web_sys::wasm_bindgen::JsCast;
+
+use crate::utils::{self, Timeout};
+
+use super::raw::RawSignal;
+use super::Signal;
+
+/// A closure called on each animation frame, which re-requests itself.
+type FrameClosure = Rc<RefCell<Option<Closure<dyn FnMut(f64)>>>>;
+
+/// Clears the interval when dropped.
+struct Interval {
+    handle: i32,
+    _closure: Closure<dyn FnMut()>,
+}
+
+impl Drop for Interval {
+    #[inline]
+    fn drop(&mut self) {
+        web_sys::window().unwrap().clear_interval_with_handle(self.handle);
+    }
+}
+
+/// Cancels the next animation frame when dropped.
+struct AnimationFrame {
+    handle: Rc<Cell<i32>>,
+    closure: FrameClosure,
+}
+
+impl Drop for AnimationFrame {
+    #[inline]
+    fn drop(&mut self) {
+        web_sys::window()
+            .unwrap()
+            .cancel_animation_frame(self.handle.get())
+            .ok();
+        // The closure holds a reference to itself, break the cycle.
+        self.closure.borrow_mut().take();
+    }
+}
+
+/// Returns a signal counting the number of times `ms` milliseconds have elapsed, starting from `0`.
+///
+/// The signal is driven by [`setInterval`](https://developer.mozilla.org/en-US/docs/Web/API/setInterval),
+/// which is cleared once the last clone of the signal is dropped.
+pub fn interval(ms: u32) -> Signal<u32> {
+    let signal = Signal::new_from_raw(RawSignal::new(Some(0u32)));
+    let weak = Rc::downgrade(signal.raw());
+
+    let closure = Closure::<dyn FnMut()>::new(move || {
+        if let Some(raw) = weak.upgrade() {
+            raw.try_mutate(|count| *count = count.wrapping_add(1)).unwrap();
+        }
+    });
+    let handle = web_sys::window()
+        .unwrap()
+        .set_interval_with_callback_and_timeout_and_arguments_0(closure.as_ref().unchecked_ref(), utils::clamp_ms(ms))
+        .unwrap();
+
+    signal.push_dependency(Interval {
+        handle,
+        _closure: closure,
+    });
+    signal
+}
+
+/// Returns a signal that is uninitialized until `ms` milliseconds have elapsed, after which it
+/// holds `value`.
+///
+/// The signal is driven by [`setTimeout`](https://developer.mozilla.org/en-US/docs/Web/API/setTimeout),
+/// which is cleared if the last clone of the signal is dropped before it fires.
+pub fn timeout<T: 'static>(ms: u32, value: T) -> Signal<T> {
+    let signal = Signal::new_from_raw(RawSignal::new(None));
+    let weak = Rc::downgrade(signal.raw());
+
+    signal.push_dependency(Timeout::new(ms, move || {
+        if let Some(raw) = weak.upgrade() {
+            raw.set(value);
+        }
+    }));
+    signal
+}
+
+/// Returns a signal holding the timestamp of the latest animation frame, in milliseconds.
+///
+/// The signal is driven by [`requestAnimationFrame`](https://developer.mozilla.org/en-US/docs/Web/API/window/requestAnimationFrame),
+/// which is cancelled once the last clone of the signal is dropped. It is uninitialized until
+/// the first frame.
+pub fn animation_frame() -> Signal<f64> {
+    let signal = Signal::new_from_raw(RawSignal::new(None));
+    let weak = Rc::downgrade(signal.raw());
+
+    let handle = Rc::new(Cell::new(0));
+    let closure = FrameClosure::default();
+
+    let (next_handle, next_closure) = (handle.clone(), closure.clone());
+    *closure.borrow_mut() = Some(Closure::new(move |time| {
+        if let Some(raw) = weak.upgrade() {
+            raw.set(time);
+            if let Some(closure) = &*next_closure.borrow() {
+                next_handle.set(request_animation_frame(closure));
+            }
+        }
+    }));
+
+    handle.set(request_animation_frame(closure.borrow().as_ref().unwrap()));
+    signal.push_dependency(AnimationFrame { handle, closure });
+    signal
+}
+
+#[inline]
+fn request_animation_frame(closure: &Closure<dyn FnMut(f64)>) -> i32 {
+    web_sys::window()
+        .unwrap()
+        .request_animation_frame(closure.as_ref().unchecked_ref())
+        .unwrap()
+}
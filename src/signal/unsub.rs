@@ -3,7 +3,7 @@ use core::ops::{Deref, DerefMut};
 
 use alloc::rc::Weak;
 
-use super::raw::{RawSignal, SubscriberId};
+use super::raw::{RawCollection, RawSignal, SubscriberId};
 
 #[must_use]
 #[repr(transparent)]
@@ -42,6 +42,43 @@ impl<T> Clone for SignalUnsubscriber<T> {
     }
 }
 
+#[must_use]
+#[repr(transparent)]
+pub struct CollectionUnsubscriber<C: 'static, D: 'static>(Option<(Weak<RawCollection<C, D>>, SubscriberId)>);
+
+impl<C, D> CollectionUnsubscriber<C, D> {
+    #[inline]
+    pub(super) fn new(weak: Weak<RawCollection<C, D>>, id: SubscriberId) -> Self {
+        Self(Some((weak, id)))
+    }
+
+    #[inline]
+    pub fn unsubscribe(&mut self) {
+        if let Some((weak, id)) = self.0.take() {
+            if let Some(raw) = weak.upgrade() {
+                raw.unsubscribe(id);
+            }
+        }
+    }
+
+    #[inline]
+    pub fn has_effect(&self) -> bool {
+        self.0.is_some()
+    }
+
+    #[inline]
+    pub fn droppable(self) -> DropUnsubscriber<Self> {
+        DropUnsubscriber(self)
+    }
+}
+
+impl<C, D> Clone for CollectionUnsubscriber<C, D> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
 #[derive(Clone)]
 #[repr(transparent)]
 pub struct DropUnsubscriber<U: Unsubscribe>(pub U);
@@ -110,3 +147,15 @@ impl<T> Unsubscribe for SignalUnsubscriber<T> {
         self.has_effect()
     }
 }
+
+impl<C, D> Unsubscribe for CollectionUnsubscriber<C, D> {
+    #[inline]
+    fn unsubscribe(&mut self) {
+        self.unsubscribe();
+    }
+
+    #[inline]
+    fn has_effect(&self) -> bool {
+        self.has_effect()
+    }
+}
@@ -1,4 +1,4 @@
-use super::{Signal, SignalMut, SignalUnsubscriber, Unsubscribe};
+use super::{Scope, Signal, SignalMut, SignalUnsubscriber, Unsubscribe};
 
 pub trait Value {
     type Item;
@@ -18,7 +18,7 @@ pub trait Value {
     where
         F: FnMut(&Self::Item) + 'static,
     {
-        _ = self.for_each(notify);
+        Scope::adopt(self.for_each(notify));
     }
 }
 
@@ -0,0 +1,398 @@
+use core::cmp::Ordering;
+use core::ops::Deref;
+
+use alloc::rc::Rc;
+use alloc::vec::Vec;
+
+use super::raw::RawCollection;
+use super::{CollectionUnsubscriber, Scope, SignalGetError};
+
+type RawSignalVec<T> = RawCollection<Vec<T>, VecDiff<T>>;
+
+pub type SignalVecUnsubscriber<T> = CollectionUnsubscriber<Vec<T>, VecDiff<T>>;
+
+/// A change made to a [`SignalVec`].
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub enum VecDiff<T> {
+    /// The whole vector was replaced. This is also the first diff received by new subscribers.
+    Replace(Vec<T>),
+    /// A value was pushed at the end of the vector.
+    Push(T),
+    /// A value was inserted at the given index.
+    Insert(usize, T),
+    /// The value at the given index was removed.
+    Remove(usize),
+    /// The values at the given indices were swapped.
+    Swap(usize, usize),
+    /// The vector was cleared.
+    Clear,
+}
+
+impl<T: Clone> VecDiff<T> {
+    /// Applies the diff to `vec`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the indices of the diff are out of bounds.
+    pub fn apply_to(&self, vec: &mut Vec<T>) {
+        match self {
+            Self::Replace(values) => vec.clone_from(values),
+            Self::Push(value) => vec.push(value.clone()),
+            Self::Insert(index, value) => vec.insert(*index, value.clone()),
+            Self::Remove(index) => drop(vec.remove(*index)),
+            Self::Swap(a, b) => vec.swap(*a, *b),
+            Self::Clear => vec.clear(),
+        }
+    }
+}
+
+/// A vector whose changes are broadcasted to its subscribers as [`VecDiff`]s, allowing them
+/// to react to each change incrementally.
+#[repr(transparent)]
+pub struct SignalVec<T: 'static>(Rc<RawSignalVec<T>>);
+
+impl<T> SignalVec<T> {
+    #[inline]
+    fn new_from_raw(raw: RawSignalVec<T>) -> Self {
+        Self(Rc::new(raw))
+    }
+
+    #[inline]
+    fn raw(&self) -> &Rc<RawSignalVec<T>> {
+        &self.0
+    }
+
+    #[inline]
+    pub fn try_len(&self) -> Result<usize, SignalGetError> {
+        self.raw().try_with(Vec::len)
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.try_len().unwrap()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    #[inline]
+    pub fn try_to_vec(&self) -> Result<Vec<T>, SignalGetError>
+    where
+        T: Clone,
+    {
+        self.raw().try_with(Vec::clone)
+    }
+
+    #[inline]
+    pub fn to_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.try_to_vec().unwrap()
+    }
+
+    #[inline]
+    fn compose<U, F>(&self, mut notify: F) -> SignalVec<U>
+    where
+        T: Clone,
+        F: FnMut(&RawSignalVec<U>, &VecDiff<T>) + 'static,
+    {
+        let vec = SignalVec::new_from_raw(RawCollection::new(Vec::new()));
+        let weak = Rc::downgrade(vec.raw());
+
+        self.for_each_inner(move |diff, unsub| match weak.upgrade() {
+            Some(raw) => notify(&raw, diff),
+            _ => unsub.unsubscribe(),
+        });
+
+        vec
+    }
+
+    /// Returns a vector with `map` applied to each value of `self`.
+    ///
+    /// `map` is only called on the values that were added to `self`.
+    pub fn map<U, F>(&self, mut map: F) -> SignalVec<U>
+    where
+        T: Clone,
+        U: Clone,
+        F: FnMut(&T) -> U + 'static,
+    {
+        self.compose(move |raw, diff| {
+            let diff = match diff {
+                VecDiff::Replace(values) => VecDiff::Replace(values.iter().map(&mut map).collect()),
+                VecDiff::Push(value) => VecDiff::Push(map(value)),
+                VecDiff::Insert(index, value) => VecDiff::Insert(*index, map(value)),
+                VecDiff::Remove(index) => VecDiff::Remove(*index),
+                VecDiff::Swap(a, b) => VecDiff::Swap(*a, *b),
+                VecDiff::Clear => VecDiff::Clear,
+            };
+            apply(raw, diff);
+        })
+    }
+
+    /// Returns a vector with only the values of `self` that satisfy `predicate`, in the same order.
+    ///
+    /// `predicate` is only called on the values that were added to `self`.
+    pub fn filter<P>(&self, mut predicate: P) -> SignalVec<T>
+    where
+        T: Clone,
+        P: FnMut(&T) -> bool + 'static,
+    {
+        // Wether each value of self is kept or not.
+        let mut mask = Vec::new();
+        let index_of = |mask: &[bool], index: usize| mask[..index].iter().filter(|&&kept| kept).count();
+
+        self.compose(move |raw, diff| match diff {
+            VecDiff::Replace(values) => {
+                mask = values.iter().map(&mut predicate).collect();
+                let values = values.iter().zip(&mask).filter(|(_, &kept)| kept);
+                apply(raw, VecDiff::Replace(values.map(|(value, _)| value.clone()).collect()));
+            }
+            VecDiff::Push(value) => {
+                let kept = predicate(value);
+                mask.push(kept);
+                if kept {
+                    apply(raw, VecDiff::Push(value.clone()));
+                }
+            }
+            VecDiff::Insert(index, value) => {
+                let kept = predicate(value);
+                mask.insert(*index, kept);
+                if kept {
+                    apply(raw, VecDiff::Insert(index_of(&mask, *index), value.clone()));
+                }
+            }
+            VecDiff::Remove(index) => {
+                if mask.remove(*index) {
+                    apply(raw, VecDiff::Remove(index_of(&mask, *index)));
+                }
+            }
+            VecDiff::Swap(a, b) => {
+                let (a, b) = (*a.min(b), *a.max(b));
+                match (mask[a], mask[b]) {
+                    (true, true) => apply(raw, VecDiff::Swap(index_of(&mask, a), index_of(&mask, b))),
+                    (false, false) => (),
+                    // A kept value moves across some other values.
+                    (kept_a, _) => {
+                        let old = index_of(&mask, if kept_a { a } else { b });
+                        mask.swap(a, b);
+                        let new = index_of(&mask, if kept_a { b } else { a });
+                        let value = raw.try_with(|values| values[old].clone()).unwrap();
+                        apply(raw, VecDiff::Remove(old));
+                        apply(raw, VecDiff::Insert(new, value));
+                        return;
+                    }
+                }
+                mask.swap(a, b);
+            }
+            VecDiff::Clear => {
+                mask.clear();
+                apply(raw, VecDiff::Clear);
+            }
+        })
+    }
+
+    /// Returns a vector with the values of `self`, sorted with `compare`.
+    ///
+    /// The sort is stable, and new values are inserted after the values they compare equal to.
+    pub fn sort_by<F>(&self, mut compare: F) -> SignalVec<T>
+    where
+        T: Clone,
+        F: FnMut(&T, &T) -> Ordering + 'static,
+    {
+        // The index in self of each value of the sorted vector.
+        let mut keys: Vec<usize> = Vec::new();
+
+        self.compose(move |raw, diff| {
+            let mut position_of = |value: &T| {
+                raw.try_with(|values| values.partition_point(|other| compare(other, value) != Ordering::Greater))
+                    .unwrap()
+            };
+
+            match diff {
+                VecDiff::Replace(values) => {
+                    keys = (0..values.len()).collect();
+                    keys.sort_by(|&a, &b| compare(&values[a], &values[b]));
+                    apply(
+                        raw,
+                        VecDiff::Replace(keys.iter().map(|&key| values[key].clone()).collect()),
+                    );
+                }
+                VecDiff::Push(value) => {
+                    let position = position_of(value);
+                    keys.insert(position, keys.len());
+                    apply(raw, VecDiff::Insert(position, value.clone()));
+                }
+                VecDiff::Insert(index, value) => {
+                    keys.iter_mut().filter(|key| **key >= *index).for_each(|key| *key += 1);
+                    let position = position_of(value);
+                    keys.insert(position, *index);
+                    apply(raw, VecDiff::Insert(position, value.clone()));
+                }
+                VecDiff::Remove(index) => {
+                    let position = keys.iter().position(|key| key == index).unwrap();
+                    keys.remove(position);
+                    keys.iter_mut().filter(|key| **key > *index).for_each(|key| *key -= 1);
+                    apply(raw, VecDiff::Remove(position));
+                }
+                VecDiff::Swap(a, b) => {
+                    // The order of the sorted vector is unaffected.
+                    for key in &mut keys {
+                        if key == a {
+                            *key = *b;
+                        } else if key == b {
+                            *key = *a;
+                        }
+                    }
+                }
+                VecDiff::Clear => {
+                    keys.clear();
+                    apply(raw, VecDiff::Clear);
+                }
+            }
+        })
+    }
+
+    #[inline]
+    pub fn for_each<F>(&self, notify: F) -> SignalVecUnsubscriber<T>
+    where
+        T: Clone,
+        F: FnMut(&VecDiff<T>) + 'static,
+    {
+        let id = self
+            .raw()
+            .raw_for_each(|_| notify, |values| VecDiff::Replace(values.clone()));
+        SignalVecUnsubscriber::new(Rc::downgrade(self.raw()), id)
+    }
+
+    #[inline]
+    pub fn for_each_inner<F>(&self, mut notify: F)
+    where
+        T: Clone,
+        F: FnMut(&VecDiff<T>, &mut SignalVecUnsubscriber<T>) + 'static,
+    {
+        let weak = Rc::downgrade(self.raw());
+        self.raw().raw_for_each(
+            |id| {
+                let mut unsub = SignalVecUnsubscriber::new(weak, id);
+                move |diff| notify(diff, &mut unsub)
+            },
+            |values| VecDiff::Replace(values.clone()),
+        );
+    }
+
+    /// See [`Signal::for_each_forever`](super::Signal::for_each_forever).
+    #[inline]
+    pub fn for_each_forever<F>(&self, notify: F)
+    where
+        T: Clone,
+        F: FnMut(&VecDiff<T>) + 'static,
+    {
+        Scope::adopt(self.for_each(notify));
+    }
+}
+
+impl<T> Clone for SignalVec<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+#[inline]
+fn apply<T: Clone>(raw: &RawSignalVec<T>, diff: VecDiff<T>) {
+    raw.try_update(|values| {
+        diff.apply_to(values);
+        Some(diff)
+    })
+    .unwrap();
+}
+
+/// A mutable [`SignalVec`].
+///
+/// # Panics
+///
+/// All methods modifying the vector panic if it is already being updated, for example when
+/// called by one of its subscribers.
+#[repr(transparent)]
+pub struct SignalVecMut<T: 'static>(SignalVec<T>);
+
+impl<T: Clone> SignalVecMut<T> {
+    #[inline]
+    pub fn new(values: Vec<T>) -> Self {
+        Self(SignalVec::new_from_raw(RawCollection::new(values)))
+    }
+
+    #[inline]
+    pub fn push(&self, value: T) {
+        apply(self.raw(), VecDiff::Push(value));
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `index > len`.
+    #[inline]
+    pub fn insert(&self, index: usize, value: T) {
+        apply(self.raw(), VecDiff::Insert(index, value));
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    #[inline]
+    pub fn remove(&self, index: usize) -> T {
+        let mut removed = None;
+        self.raw()
+            .try_update(|values| {
+                removed = Some(values.remove(index));
+                Some(VecDiff::Remove(index))
+            })
+            .unwrap();
+        removed.unwrap()
+    }
+
+    /// # Panics
+    ///
+    /// Panics if `a` or `b` are out of bounds.
+    #[inline]
+    pub fn swap(&self, a: usize, b: usize) {
+        apply(self.raw(), VecDiff::Swap(a, b));
+    }
+
+    #[inline]
+    pub fn clear(&self) {
+        apply(self.raw(), VecDiff::Clear);
+    }
+}
+
+impl<T> Clone for SignalVecMut<T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<T> Deref for SignalVecMut<T> {
+    type Target = SignalVec<T>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T: Clone> Default for SignalVecMut<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new(Vec::new())
+    }
+}
+
+impl<T: Clone> From<Vec<T>> for SignalVecMut<T> {
+    #[inline]
+    fn from(values: Vec<T>) -> Self {
+        Self::new(values)
+    }
+}
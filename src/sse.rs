@@ -0,0 +1,365 @@
+//! [Server-sent events](https://developer.mozilla.org/en-US/docs/Web/API/Server-sent_events), received
+//! as signals or streams.
+
+use core::cell::{Cell, RefCell};
+use core::fmt;
+use core::pin::Pin;
+use core::task::{Context, Poll, Waker};
+
+use alloc::collections::VecDeque;
+use alloc::rc::{Rc, Weak};
+use alloc::string::{String, ToString};
+use alloc::vec;
+use alloc::vec::Vec;
+use futures::stream::Stream;
+use web_sys::wasm_bindgen::closure::Closure;
+use web_sys::wasm_bindgen::JsCast;
+use web_sys::{EventSourceInit, MessageEvent};
+
+use crate::fetch::RetryPolicy;
+use crate::signal::{Signal, SignalMut};
+use crate::utils::Timeout;
+
+/// A message received from an [`EventSource`].
+#[derive(Clone, PartialEq, Eq, Hash, Default, Debug)]
+pub struct Message {
+    /// The type of the event, `message` unless the server named it.
+    pub event: String,
+    /// The data of the event, its lines joined with `\n`.
+    pub data: String,
+    /// The ID of the latest event received, if the server set one.
+    pub last_event_id: String,
+}
+
+impl Message {
+    /// Deserializes the data of the message from JSON.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    #[inline]
+    pub fn json<T: serde::de::DeserializeOwned>(&self) -> Result<T, serde_json::Error> {
+        serde_json::from_str(&self.data)
+    }
+}
+
+/// A connection to a server sending events, using an
+/// [`EventSource`](https://developer.mozilla.org/en-US/docs/Web/API/EventSource).
+///
+/// Like [`Fetch`](crate::fetch::Fetch), the connection is only described on the rust side, and is opened
+/// by [`EventSource::open`] or [`EventSource::stream`]. The connection is closed when the returned
+/// [`Connection`] or stream is dropped.
+///
+/// When the connection is lost, the browser reconnects on its own, sending back the ID of the last event
+/// received. When the browser gives up (for example when the server responds with an error status), the
+/// connection is opened again after a delay given by its [retry policy](EventSource::with_retry). Since
+/// that connection is new, the ID of the last event received is not sent back to the server.
+#[derive(Clone, Debug)]
+pub struct EventSource {
+    url: String,
+    credentials: bool,
+    events: Vec<String>,
+    retry: RetryPolicy,
+}
+
+impl EventSource {
+    /// Creates a connection receiving the unnamed events sent to `url`, retrying forever.
+    #[inline]
+    pub fn new<U: ToString>(url: U) -> Self {
+        Self {
+            url: url.to_string(),
+            credentials: false,
+            events: vec!["message".to_string()],
+            retry: RetryPolicy::new(u32::MAX),
+        }
+    }
+
+    #[inline]
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    /// Sends the cookies of the user with cross-origin connections.
+    #[inline]
+    pub fn with_credentials(&mut self, credentials: bool) -> &mut Self {
+        self.credentials = credentials;
+        self
+    }
+
+    /// Also receives the events of type `event`, that the server named with an `event:` field.
+    #[inline]
+    pub fn with_event(&mut self, event: &str) -> &mut Self {
+        if !self.events.iter().any(|other| other == event) {
+            self.events.push(event.to_string());
+        }
+        self
+    }
+
+    /// Opens the connection again according to `retry` when it fails, the count of attempts being
+    /// reset once it succeeds.
+    #[inline]
+    pub fn with_retry(&mut self, retry: RetryPolicy) -> &mut Self {
+        self.retry = retry;
+        self
+    }
+
+    /// Opens the connection, returning it along with a signal holding the latest message received.
+    ///
+    /// The signal is uninitialized until the first message is received. The connection stays open, and the
+    /// subscribers of the signal keep being notified, for as long as the returned [`Connection`] is held.
+    #[inline]
+    pub fn open(&self) -> (Connection, Signal<Message>) {
+        self.open_with(Some)
+    }
+
+    /// Same as [`EventSource::open`], deserializing the data of the messages from JSON.
+    ///
+    /// Messages that can't be deserialized are ignored.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    #[inline]
+    pub fn open_json<T>(&self) -> (Connection, Signal<T>)
+    where
+        T: serde::de::DeserializeOwned + 'static,
+    {
+        self.open_with(|message| message.json().ok())
+    }
+
+    /// Opens the connection, returning a stream of all the messages received.
+    ///
+    /// Unlike the signal returned by [`EventSource::open`], no message is skipped if the stream is
+    /// polled less often than messages are received.
+    #[inline]
+    pub fn stream(&self) -> MessageStream<Message> {
+        self.stream_with(Some)
+    }
+
+    /// Same as [`EventSource::stream`], deserializing the data of the messages from JSON.
+    #[cfg(feature = "json")]
+    #[cfg_attr(docsrs, doc(cfg(feature = "json")))]
+    #[inline]
+    pub fn stream_json<T>(&self) -> MessageStream<Result<T, serde_json::Error>>
+    where
+        T: serde::de::DeserializeOwned + 'static,
+    {
+        self.stream_with(|message| Some(message.json()))
+    }
+
+    fn open_with<T, F>(&self, decode: F) -> (Connection, Signal<T>)
+    where
+        T: 'static,
+        F: Fn(Message) -> Option<T> + 'static,
+    {
+        // The connection keeps the signal alive, so that its subscribers keep receiving messages.
+        let signal = SignalMut::uninit();
+        let setter = signal.clone();
+        let connection = Connection::open(self, move |message| {
+            if let Some(value) = decode(message) {
+                setter.set(value);
+            }
+        });
+        (connection, (*signal).clone())
+    }
+
+    fn stream_with<T, F>(&self, decode: F) -> MessageStream<T>
+    where
+        T: 'static,
+        F: Fn(Message) -> Option<T> + 'static,
+    {
+        let queue = Rc::new(RefCell::new(Queue {
+            messages: VecDeque::new(),
+            waker: None,
+        }));
+
+        let weak = Rc::downgrade(&queue);
+        let connection = Connection::open(self, move |message| {
+            let (Some(queue), Some(value)) = (weak.upgrade(), decode(message)) else {
+                return;
+            };
+            let mut queue = queue.borrow_mut();
+            queue.messages.push_back(value);
+            if let Some(waker) = queue.waker.take() {
+                waker.wake();
+            }
+        });
+
+        MessageStream {
+            queue,
+            _connection: connection,
+        }
+    }
+}
+
+struct Queue<T> {
+    messages: VecDeque<T>,
+    waker: Option<Waker>,
+}
+
+/// A [`Stream`] of the messages received by an [`EventSource`], see [`EventSource::stream`].
+///
+/// The connection is closed when the stream is dropped, the stream never ends otherwise.
+#[must_use = "streams do nothing unless polled"]
+pub struct MessageStream<T> {
+    queue: Rc<RefCell<Queue<T>>>,
+    _connection: Connection,
+}
+
+impl<T> Stream for MessageStream<T> {
+    type Item = T;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let mut queue = self.queue.borrow_mut();
+        match queue.messages.pop_front() {
+            Some(message) => Poll::Ready(Some(message)),
+            _ => {
+                queue.waker = Some(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
+
+struct ConnectionInner {
+    url: String,
+    credentials: bool,
+    events: Vec<String>,
+    retry: RetryPolicy,
+    source: RefCell<Option<web_sys::EventSource>>,
+    /// The number of consecutive failed attempts to connect.
+    attempt: Cell<u32>,
+    reconnect: RefCell<Option<Timeout>>,
+    on_message: Closure<dyn FnMut(MessageEvent)>,
+    on_open: Closure<dyn FnMut()>,
+    on_error: Closure<dyn FnMut()>,
+}
+
+/// An open connection of an [`EventSource`], see [`EventSource::open`].
+///
+/// The connection is closed when dropped.
+#[must_use = "the connection is closed when dropped"]
+pub struct Connection(Rc<ConnectionInner>);
+
+impl Connection {
+    fn open<F>(source: &EventSource, mut notify: F) -> Self
+    where
+        F: FnMut(Message) + 'static,
+    {
+        let inner = Rc::new_cyclic(|weak: &Weak<ConnectionInner>| {
+            let on_message = Closure::<dyn FnMut(MessageEvent)>::new(move |event: MessageEvent| {
+                notify(Message {
+                    event: event.type_(),
+                    data: event.data().as_string().unwrap_or_default(),
+                    last_event_id: event.last_event_id(),
+                });
+            });
+
+            let on_open = Closure::<dyn FnMut()>::new({
+                let weak = weak.clone();
+                move || {
+                    if let Some(inner) = weak.upgrade() {
+                        inner.attempt.set(0);
+                    }
+                }
+            });
+
+            let on_error = Closure::<dyn FnMut()>::new({
+                let weak = weak.clone();
+                move || {
+                    let Some(inner) = weak.upgrade() else {
+                        return;
+                    };
+                    // The browser is reconnecting on its own, unless the connection is closed.
+                    let closed = inner
+                        .source
+                        .borrow()
+                        .as_ref()
+                        .is_some_and(|source| source.ready_state() == web_sys::EventSource::CLOSED);
+                    if closed {
+                        ConnectionInner::fail(&inner);
+                    }
+                }
+            });
+
+            ConnectionInner {
+                url: source.url.clone(),
+                credentials: source.credentials,
+                events: source.events.clone(),
+                retry: source.retry.clone(),
+                source: RefCell::new(None),
+                attempt: Cell::new(0),
+                reconnect: RefCell::new(None),
+                on_message,
+                on_open,
+                on_error,
+            }
+        });
+
+        ConnectionInner::connect(&inner);
+        Self(inner)
+    }
+}
+
+impl ConnectionInner {
+    /// Opens a new connection, failing if the browser can't create it (for example if the url is invalid).
+    fn connect(this: &Rc<Self>) {
+        let init = EventSourceInit::new();
+        init.set_with_credentials(this.credentials);
+        let Ok(source) = web_sys::EventSource::new_with_event_source_init_dict(&this.url, &init) else {
+            Self::fail(this);
+            return;
+        };
+
+        for event in &this.events {
+            source
+                .add_event_listener_with_callback(event, this.on_message.as_ref().unchecked_ref())
+                .unwrap();
+        }
+        source.set_onopen(Some(this.on_open.as_ref().unchecked_ref()));
+        source.set_onerror(Some(this.on_error.as_ref().unchecked_ref()));
+        this.source.replace(Some(source));
+    }
+
+    /// Closes the current connection, and opens a new one after a delay if the retry policy allows it.
+    fn fail(this: &Rc<Self>) {
+        this.close();
+
+        let attempt = this.attempt.get() + 1;
+        this.attempt.set(attempt);
+        if attempt >= this.retry.max_attempts() {
+            return;
+        }
+
+        let weak = Rc::downgrade(this);
+        let timeout = Timeout::new(this.retry.backoff(attempt), move || {
+            if let Some(inner) = weak.upgrade() {
+                Self::connect(&inner);
+            }
+        });
+        this.reconnect.replace(Some(timeout));
+    }
+
+    #[inline]
+    fn close(&self) {
+        if let Some(source) = self.source.take() {
+            source.set_onopen(None);
+            source.set_onerror(None);
+            source.close();
+        }
+    }
+}
+
+impl fmt::Debug for Connection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Connection")
+            .field("url", &self.0.url)
+            .field("events", &self.0.events)
+            .field("attempt", &self.0.attempt.get())
+            .finish()
+    }
+}
+
+impl Drop for Connection {
+    #[inline]
+    fn drop(&mut self) {
+        self.0.reconnect.take();
+        self.0.close();
+    }
+}
@@ -5,6 +5,10 @@ use alloc::boxed::Box;
 use alloc::rc::Rc;
 use alloc::string::String;
 use alloc::sync::Arc;
+use wasm_bindgen_futures::JsFuture;
+use web_sys::js_sys::Promise;
+use web_sys::wasm_bindgen::closure::Closure;
+use web_sys::wasm_bindgen::JsCast;
 
 macro_rules! for_all_tuples {
     ($mac: ident) => {
@@ -34,6 +38,50 @@ macro_rules! for_all_tuples {
 
 pub(crate) use for_all_tuples;
 
+/// Declares a global value that is local to the current thread.
+///
+/// On wasm there is only ever one thread, so a plain static suffices. Elsewhere (e.g. when running
+/// tests natively), this defers to [`std::thread_local!`].
+macro_rules! local {
+    ($(#[$attr: meta])* static $name: ident: $ty: ty = $init: expr;) => {
+        #[cfg(target_arch = "wasm32")]
+        $(#[$attr])*
+        static $name: $crate::utils::LocalKey<$ty> = $crate::utils::LocalKey::new($init);
+
+        #[cfg(not(target_arch = "wasm32"))]
+        std::thread_local! {
+            $(#[$attr])*
+            static $name: $ty = const { $init };
+        }
+    };
+}
+
+pub(crate) use local;
+
+/// A global value on a single-threaded target, see [`local!`].
+#[cfg(target_arch = "wasm32")]
+pub(crate) struct LocalKey<T: 'static>(T);
+
+// SAFETY: wasm32-unknown-unknown is single-threaded.
+#[cfg(target_arch = "wasm32")]
+unsafe impl<T> Sync for LocalKey<T> {}
+
+#[cfg(target_arch = "wasm32")]
+impl<T> LocalKey<T> {
+    #[inline]
+    pub const fn new(value: T) -> Self {
+        Self(value)
+    }
+
+    #[inline]
+    pub fn with<F, R>(&'static self, f: F) -> R
+    where
+        F: FnOnce(&T) -> R,
+    {
+        f(&self.0)
+    }
+}
+
 pub trait TryAsRef<T>
 where
     T: ?Sized,
@@ -143,10 +191,164 @@ where
     }
 }
 
+/// Runs `future` on the current thread.
+///
+/// Elsewhere than on wasm (e.g. when running tests natively), there is no event loop to run it:
+/// it is queued until [`run_until_stalled`] is called.
 #[inline]
 pub fn spawn<F>(future: F)
 where
     F: Future<Output = ()> + 'static,
 {
+    #[cfg(target_arch = "wasm32")]
     wasm_bindgen_futures::spawn_local(future);
+
+    #[cfg(not(target_arch = "wasm32"))]
+    executor::spawn(Box::pin(future));
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub use executor::run_until_stalled;
+
+/// Runs the futures given to [`spawn`] when there is no event loop, see [`run_until_stalled`].
+#[cfg(not(target_arch = "wasm32"))]
+mod executor {
+    use core::cell::RefCell;
+    use core::future::Future;
+    use core::mem;
+    use core::pin::Pin;
+    use core::sync::atomic::{AtomicBool, Ordering};
+    use core::task::{Context, Waker};
+
+    use alloc::boxed::Box;
+    use alloc::sync::Arc;
+    use alloc::task::Wake;
+    use alloc::vec::Vec;
+
+    struct Task {
+        future: Pin<Box<dyn Future<Output = ()>>>,
+        /// Whether the task was woken since it was last polled.
+        woken: Arc<Woken>,
+    }
+
+    struct Woken(AtomicBool);
+
+    impl Wake for Woken {
+        #[inline]
+        fn wake(self: Arc<Self>) {
+            self.0.store(true, Ordering::Relaxed);
+        }
+    }
+
+    local! {
+        static TASKS: RefCell<Vec<Task>> = RefCell::new(Vec::new());
+    }
+
+    #[inline]
+    pub(super) fn spawn(future: Pin<Box<dyn Future<Output = ()>>>) {
+        let woken = Arc::new(Woken(AtomicBool::new(true)));
+        TASKS.with(|tasks| tasks.borrow_mut().push(Task { future, woken }));
+    }
+
+    /// Polls the futures given to [`spawn`](super::spawn) until none of them can make progress.
+    ///
+    /// Only available natively (e.g. in tests), the futures being run by the event loop on wasm.
+    pub fn run_until_stalled() {
+        loop {
+            let tasks = TASKS.with(|tasks| mem::take(&mut *tasks.borrow_mut()));
+            let mut progressed = false;
+            let mut pending = Vec::new();
+            for mut task in tasks {
+                if task.woken.0.swap(false, Ordering::Relaxed) {
+                    progressed = true;
+                    let waker = Waker::from(task.woken.clone());
+                    if task.future.as_mut().poll(&mut Context::from_waker(&waker)).is_ready() {
+                        continue;
+                    }
+                }
+                pending.push(task);
+            }
+
+            // The futures spawned while polling go after the pending ones.
+            TASKS.with(|tasks| {
+                let mut tasks = tasks.borrow_mut();
+                pending.append(&mut tasks);
+                *tasks = pending;
+            });
+            if !progressed {
+                break;
+            }
+        }
+    }
+}
+
+/// Returns a random number in the range [0, 1), using [`Math.random()`](https://developer.mozilla.org/en-US/docs/Web/JavaScript/Reference/Global_Objects/Math/random) in the browser.
+///
+/// Elsewhere (e.g. when running tests natively), the randomly seeded hasher of the standard library
+/// is used instead.
+#[inline]
+#[cfg(feature = "fetch")]
+pub(crate) fn random() -> f64 {
+    #[cfg(target_arch = "wasm32")]
+    return web_sys::js_sys::Math::random();
+
+    #[cfg(not(target_arch = "wasm32"))]
+    {
+        use std::hash::{BuildHasher, Hasher};
+        let bits = std::collections::hash_map::RandomState::new().build_hasher().finish();
+        (bits >> 11) as f64 / (1u64 << 53) as f64
+    }
+}
+
+/// Returns a future that resolves after `ms` milliseconds, using [`setTimeout`](https://developer.mozilla.org/en-US/docs/Web/API/setTimeout).
+#[inline]
+pub async fn sleep(ms: u32) {
+    let promise = Promise::new(&mut |resolve, _| {
+        web_sys::window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(&resolve, clamp_ms(ms))
+            .unwrap();
+    });
+    JsFuture::from(promise).await.unwrap();
+}
+
+/// A callback scheduled with [`setTimeout`](https://developer.mozilla.org/en-US/docs/Web/API/setTimeout).
+///
+/// The timeout is cleared when this handle is dropped, if it did not fire already.
+#[derive(Debug)]
+pub struct Timeout {
+    handle: i32,
+    _closure: Closure<dyn FnMut()>,
+}
+
+impl Timeout {
+    #[inline]
+    pub fn new<F>(ms: u32, callback: F) -> Self
+    where
+        F: FnOnce() + 'static,
+    {
+        let closure = Closure::once(callback);
+        let handle = web_sys::window()
+            .unwrap()
+            .set_timeout_with_callback_and_timeout_and_arguments_0(closure.as_ref().unchecked_ref(), clamp_ms(ms))
+            .unwrap();
+
+        Self {
+            handle,
+            _closure: closure,
+        }
+    }
+}
+
+impl Drop for Timeout {
+    #[inline]
+    fn drop(&mut self) {
+        web_sys::window().unwrap().clear_timeout_with_handle(self.handle);
+    }
+}
+
+/// Browsers store delays as signed 32 bits integers.
+#[inline]
+pub(crate) fn clamp_ms(ms: u32) -> i32 {
+    ms.min(i32::MAX as u32) as i32
 }
@@ -1,9 +1,15 @@
+use core::mem;
+use core::ops::Bound;
+
 use alloc::borrow::Cow;
+use alloc::collections::BTreeMap;
+use alloc::rc::Rc;
 use alloc::string::String;
-use web_sys::{Element, Text};
+use alloc::vec::Vec;
+use web_sys::{Element, Node, Text};
 
-use crate::component::Component;
-use crate::signal::{Unsubscribe, Value};
+use crate::component::{Component, ElementNotFoundError};
+use crate::signal::{MapDiff, Pending, Signal, SignalMap, SignalMut, SignalVec, Unsubscribe, Value, VecDiff};
 use crate::utils::for_all_tuples;
 
 mod utils {
@@ -31,6 +37,16 @@ mod utils {
         div
     }
 
+    /// Removes the component from the DOM when dropped.
+    pub struct Detach(pub Component);
+
+    impl Drop for Detach {
+        #[inline]
+        fn drop(&mut self) {
+            self.0.as_element().remove();
+        }
+    }
+
     #[inline]
     pub fn swap_elements(parent: &Element, old: &Element, new: &Element) {
         parent.insert_before(new, Some(old)).unwrap();
@@ -197,7 +213,7 @@ impl UpdateableView for Option<Cow<'_, str>> {
 impl View for Component {
     #[inline]
     fn init(&self, parent: &Component) {
-        parent.as_element().append_child(&self.as_element()).unwrap();
+        parent.as_element().append_child(self.as_element()).unwrap();
         parent.push_dependency(self.clone());
     }
 }
@@ -217,20 +233,359 @@ impl UpdateableView for Component {
     }
 }
 
+/// Shows the [`Component`] built by `F` only while `C` is `true`.
 pub struct If<C, F>(pub C, pub F);
 
 impl<C, F> View for If<C, F>
 where
     C: Value<Item = bool>,
-    F: FnOnce() -> Component,
+    F: Fn() -> Component,
 {
     fn init(&self, parent: &Component) {
         let weak = parent.downgrade();
+        let component = (self.1)();
         let placeholder = utils::placeholder_div(parent);
+        let mut visible = false;
         let unsub = self.0.for_each(move |&cond| {
             if let Some(parent) = weak.upgrade() {
-                if cond {
-                    utils::swap_elements(parent.as_element(), todo!(), todo!());
+                if cond != visible {
+                    let (old, new) = match cond {
+                        true => (&placeholder, component.as_element()),
+                        false => (component.as_element(), &placeholder),
+                    };
+                    utils::swap_elements(parent.as_element(), old, new);
+                    visible = cond;
+                }
+            }
+        });
+        parent.push_dependency(unsub.droppable());
+    }
+}
+
+/// Shows the fallback [`Component`] while some of the asynchronous signals created when building
+/// the content with `F` are still uninitialized, then swaps in the content.
+///
+/// Asynchronous signals are the ones created with [`Signal::from_future`](crate::signal::Signal::from_future)
+/// or [`Signal::from_stream`](crate::signal::Signal::from_stream), see [`Pending`] for more details.
+pub struct Suspense<F>(pub Component, pub F);
+
+impl<F> View for Suspense<F>
+where
+    F: Fn() -> Component,
+{
+    fn init(&self, parent: &Component) {
+        let pending = Pending::new();
+        let content = pending.track(&self.1);
+
+        if !pending.is_pending() {
+            content.init(parent);
+            return;
+        }
+
+        let weak = parent.downgrade();
+        let fallback = self.0.clone();
+        parent.as_element().append_child(fallback.as_element()).unwrap();
+        parent.push_dependency(fallback.clone());
+        parent.push_dependency(content.clone());
+
+        let mut ready = false;
+        let unsub = pending.count().for_each(move |&count| {
+            if let Some(parent) = weak.upgrade() {
+                if count == 0 && !ready {
+                    utils::swap_elements(parent.as_element(), fallback.as_element(), content.as_element());
+                    ready = true;
+                }
+            }
+        });
+        parent.push_dependency(unsub.droppable());
+    }
+}
+
+/// A handle to an error boundary, see [`Catch`].
+///
+/// Errors thrown to the boundary replace its content with the fallback [`Component`], until the
+/// boundary is [`reset`](ErrorBoundary::reset).
+pub struct ErrorBoundary<E: 'static>(SignalMut<Option<Rc<E>>>);
+
+impl<E> ErrorBoundary<E> {
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The error currently caught by the boundary, if any.
+    #[inline]
+    pub fn error(&self) -> &Signal<Option<Rc<E>>> {
+        &self.0
+    }
+
+    /// Reports an error to the boundary.
+    ///
+    /// If the boundary is currently updating, the error is reported asynchronously.
+    pub fn throw(&self, error: E) {
+        let error = Some(Rc::new(error));
+        if self.0.try_set(error.clone()).is_err() {
+            let signal = self.0.clone();
+            crate::utils::spawn(async move { signal.set(error) });
+        }
+    }
+
+    /// Clears the error and rebuilds the content of the boundary.
+    #[inline]
+    pub fn reset(&self) {
+        self.0.set(None);
+    }
+
+    /// Returns a signal with the successful values of `signal`, errors being thrown to the boundary.
+    #[inline]
+    pub fn catch<T>(&self, signal: &Signal<Result<T, E>>) -> Signal<T>
+    where
+        T: Clone,
+        E: Clone,
+    {
+        let boundary = self.clone();
+        signal.filter_map(move |result| match result {
+            Ok(value) => Some(value.clone()),
+            Err(error) => {
+                boundary.throw(error.clone());
+                None
+            }
+        })
+    }
+}
+
+impl<E> Clone for ErrorBoundary<E> {
+    #[inline]
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<E> Default for ErrorBoundary<E> {
+    #[inline]
+    fn default() -> Self {
+        Self(SignalMut::new(None))
+    }
+}
+
+/// Shows the [`Component`] built by `C`, or the fallback built by `F` when `C` fails or when an error
+/// is thrown to the [`ErrorBoundary`].
+///
+/// Resetting the boundary builds the content anew.
+pub struct Catch<E: 'static, C, F>(pub ErrorBoundary<E>, pub C, pub F);
+
+impl<E, C, F> View for Catch<E, C, F>
+where
+    C: Fn() -> Result<Component, E> + Clone + 'static,
+    F: Fn(&E) -> Component + Clone + 'static,
+{
+    fn init(&self, parent: &Component) {
+        let weak = parent.downgrade();
+        let content = self.1.clone();
+        let fallback = self.2.clone();
+        let placeholder = utils::placeholder_div(parent);
+        let mut current: Option<Component> = None;
+        let unsub = self.0.error().for_each(move |error| {
+            if let Some(parent) = weak.upgrade() {
+                let new = match error {
+                    Some(error) => fallback(error),
+                    None => content().unwrap_or_else(|error| fallback(&error)),
+                };
+                let old = current.as_ref().map_or(&placeholder, Component::as_element);
+                utils::swap_elements(parent.as_element(), old, new.as_element());
+                current = Some(new);
+            }
+        });
+        parent.push_dependency(unsub.droppable());
+    }
+}
+
+/// The element a [`Portal`] renders into.
+pub trait PortalTarget {
+    fn target(&self) -> Result<Element, ElementNotFoundError>;
+}
+
+/// The first element matching the selectors, as returned by [`document.querySelector(selectors)`](https://developer.mozilla.org/en-US/docs/Web/API/Document/querySelector).
+impl PortalTarget for &str {
+    #[inline]
+    fn target(&self) -> Result<Element, ElementNotFoundError> {
+        web_sys::window()
+            .unwrap()
+            .document()
+            .unwrap()
+            .query_selector(self)
+            .ok()
+            .flatten()
+            .ok_or(ElementNotFoundError)
+    }
+}
+
+impl PortalTarget for String {
+    #[inline]
+    fn target(&self) -> Result<Element, ElementNotFoundError> {
+        self.as_str().target()
+    }
+}
+
+impl PortalTarget for Element {
+    #[inline]
+    fn target(&self) -> Result<Element, ElementNotFoundError> {
+        Ok(self.clone())
+    }
+}
+
+impl PortalTarget for Component {
+    #[inline]
+    fn target(&self) -> Result<Element, ElementNotFoundError> {
+        Ok(self.as_element().clone())
+    }
+}
+
+/// Renders the [`Component`] into another location of the DOM, given by `T`, instead of the parent.
+///
+/// The component is still owned by the parent, and is removed from the DOM when the parent is dropped.
+///
+/// # Panics
+///
+/// Panics if the target element can't be found.
+pub struct Portal<T>(pub T, pub Component);
+
+impl<T: PortalTarget> View for Portal<T> {
+    fn init(&self, parent: &Component) {
+        let target = self.0.target().unwrap();
+        target.append_child(self.1.as_element()).unwrap();
+        parent.push_dependency(utils::Detach(self.1.clone()));
+    }
+}
+
+/// Shows a [`Component`] built by `F` for each value of the [`SignalVec`], applying each change to
+/// the vector directly to the DOM.
+pub struct List<T: 'static, F>(pub SignalVec<T>, pub F);
+
+impl<T, F> View for List<T, F>
+where
+    T: Clone,
+    F: Fn(&T) -> Component + Clone + 'static,
+{
+    fn init(&self, parent: &Component) {
+        let weak = parent.downgrade();
+        let render = self.1.clone();
+        let anchor = utils::text_node("", parent);
+        let mut items: Vec<Component> = Vec::new();
+
+        let unsub = self.0.for_each(move |diff| {
+            let Some(parent) = weak.upgrade() else {
+                return;
+            };
+            let parent = parent.as_element();
+            let node_at = |items: &[Component], index: usize| -> Node {
+                match items.get(index) {
+                    Some(item) => item.as_element().clone().into(),
+                    None => anchor.clone().into(),
+                }
+            };
+
+            match diff {
+                VecDiff::Replace(values) => {
+                    items.drain(..).for_each(|item| item.as_element().remove());
+                    for value in values {
+                        let item = render(value);
+                        parent.insert_before(item.as_element(), Some(&anchor)).unwrap();
+                        items.push(item);
+                    }
+                }
+                VecDiff::Push(value) => {
+                    let item = render(value);
+                    parent.insert_before(item.as_element(), Some(&anchor)).unwrap();
+                    items.push(item);
+                }
+                VecDiff::Insert(index, value) => {
+                    let item = render(value);
+                    parent
+                        .insert_before(item.as_element(), Some(&node_at(&items, *index)))
+                        .unwrap();
+                    items.insert(*index, item);
+                }
+                VecDiff::Remove(index) => {
+                    items.remove(*index).as_element().remove();
+                }
+                VecDiff::Swap(a, b) => {
+                    let (a, b) = (*a.min(b), *a.max(b));
+                    if a != b {
+                        let after_b = node_at(&items, b + 1);
+                        parent
+                            .insert_before(items[b].as_element(), Some(items[a].as_element()))
+                            .unwrap();
+                        parent.insert_before(items[a].as_element(), Some(&after_b)).unwrap();
+                        items.swap(a, b);
+                    }
+                }
+                VecDiff::Clear => {
+                    items.drain(..).for_each(|item| item.as_element().remove());
+                }
+            }
+        });
+        parent.push_dependency(unsub.droppable());
+    }
+}
+
+/// Shows a [`Component`] built by `F` for each entry of the [`SignalMap`], in the order of the keys,
+/// applying each change to the map directly to the DOM.
+pub struct KeyedList<K: 'static, V: 'static, F>(pub SignalMap<K, V>, pub F);
+
+impl<K, V, F> View for KeyedList<K, V, F>
+where
+    K: Ord + Clone,
+    V: Clone,
+    F: Fn(&K, &V) -> Component + Clone + 'static,
+{
+    fn init(&self, parent: &Component) {
+        let weak = parent.downgrade();
+        let render = self.1.clone();
+        let anchor = utils::text_node("", parent);
+        let mut items: BTreeMap<K, Component> = BTreeMap::new();
+
+        let unsub = self.0.for_each(move |diff| {
+            let Some(parent) = weak.upgrade() else {
+                return;
+            };
+            let parent = parent.as_element();
+
+            match diff {
+                MapDiff::Replace(entries) => {
+                    mem::take(&mut items)
+                        .into_values()
+                        .for_each(|item| item.as_element().remove());
+                    for (key, value) in entries {
+                        let item = render(key, value);
+                        parent.insert_before(item.as_element(), Some(&anchor)).unwrap();
+                        items.insert(key.clone(), item);
+                    }
+                }
+                MapDiff::Insert(key, value) => {
+                    let item = render(key, value);
+                    let next: Node = match items.range((Bound::Excluded(key), Bound::Unbounded)).next() {
+                        Some((_, next)) => next.as_element().clone().into(),
+                        None => anchor.clone().into(),
+                    };
+                    parent.insert_before(item.as_element(), Some(&next)).unwrap();
+                    items.insert(key.clone(), item);
+                }
+                MapDiff::Update(key, value) => {
+                    let item = render(key, value);
+                    if let Some(old) = items.insert(key.clone(), item.clone()) {
+                        utils::swap_elements(parent, old.as_element(), item.as_element());
+                    }
+                }
+                MapDiff::Remove(key) => {
+                    if let Some(item) = items.remove(key) {
+                        item.as_element().remove();
+                    }
+                }
+                MapDiff::Clear => {
+                    mem::take(&mut items)
+                        .into_values()
+                        .for_each(|item| item.as_element().remove());
                 }
             }
         });
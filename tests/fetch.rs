@@ -0,0 +1,344 @@
+#![cfg(feature = "fetch")]
+
+use std::cell::Cell;
+use std::future::{self, Future};
+use std::pin::pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use futures::StreamExt;
+use wasmadeus::fetch::{
+    encode_uri_component, ByteStream, Client, Fetch, FetchError, FormUrlEncoded, HeaderError, HeaderMap, Method,
+    MockRequest, MockTransport, Progress, QueryCache, QueryKey, RequestBody, Response, RetryPolicy, Text,
+};
+use wasmadeus::signal::ManualClock;
+use wasmadeus::utils::run_until_stalled;
+
+/// Polls a future that must be ready right away.
+fn now<F: Future>(future: F) -> F::Output {
+    match pin!(future).poll(&mut Context::from_waker(Waker::noop())) {
+        Poll::Ready(output) => output,
+        Poll::Pending => panic!("future is pending"),
+    }
+}
+
+#[test]
+fn retry_backoff() {
+    let retry = RetryPolicy::new(10).with_jitter(false).with_max_delay(1500);
+    let delays: Vec<_> = (1..=5).map(|attempt| retry.backoff(attempt)).collect();
+    assert_eq!(delays, [250, 500, 1000, 1500, 1500]);
+    assert_eq!(retry.backoff(40), 1500);
+    assert!(retry.retries_status(503) && !retry.retries_status(500));
+}
+
+#[test]
+fn progress_ratio() {
+    let progress = |received, total| Progress { received, total };
+    assert_eq!(progress(50, Some(200)).ratio(), Some(0.25));
+    assert_eq!(progress(300, Some(200)).ratio(), Some(1.0));
+    assert_eq!(progress(0, Some(0)).ratio(), Some(1.0));
+    assert_eq!(progress(50, None).ratio(), None);
+}
+
+#[test]
+fn byte_stream_progress() {
+    let transport = MockTransport::new();
+    let mut headers = HeaderMap::new();
+    headers.insert("Content-Length", "11").unwrap();
+    transport.mock(
+        MockRequest::new(Method::Get, "/file"),
+        Response::new(200, headers, "hello world"),
+    );
+
+    let mut stream = now(Fetch::get("/file").with_transport(transport).execute::<ByteStream>()).unwrap();
+    assert_eq!(
+        stream.progress().get(),
+        Progress {
+            received: 0,
+            total: Some(11)
+        }
+    );
+
+    let chunks = now(stream.by_ref().collect::<Vec<_>>());
+    let bytes: Vec<u8> = chunks.into_iter().flat_map(Result::unwrap).collect();
+    assert_eq!(bytes, b"hello world");
+    assert_eq!(
+        stream.progress().get(),
+        Progress {
+            received: 11,
+            total: Some(11)
+        }
+    );
+}
+
+#[test]
+fn uri_component() {
+    assert_eq!(encode_uri_component("a b&c=d/é"), "a%20b%26c%3Dd%2F%C3%A9");
+    assert_eq!(encode_uri_component("safe-_.!~*'()"), "safe-_.!~*'()");
+}
+
+#[test]
+fn url_builder() {
+    let mut fetch = Fetch::get("/users/{id}/posts#top");
+    fetch
+        .with_base_url("https://example.com/api/")
+        .with_path_param("id", "a/b")
+        .with_query(&[("q", "x y"), ("page", "2")]);
+    assert_eq!(
+        fetch.url(),
+        "https://example.com/api/users/a%2Fb/posts?q=x%20y&page=2#top"
+    );
+
+    assert_eq!(
+        Fetch::get("https://example.com").with_base_url("/api").url(),
+        "https://example.com"
+    );
+}
+
+#[test]
+fn headers() {
+    let mut fetch = Fetch::get("/");
+    fetch.with_header("X-Request-Id", "1").unwrap();
+    fetch.with_basic_auth("aladdin", Some("opensesame")).unwrap();
+    fetch.with_header("x-request-id", "2").unwrap();
+
+    let headers = fetch.headers();
+    assert_eq!(headers.get("X-REQUEST-ID"), Some("2"));
+    assert_eq!(headers.get("authorization"), Some("Basic YWxhZGRpbjpvcGVuc2VzYW1l"));
+    assert_eq!(headers.len(), 2);
+
+    assert!(matches!(
+        fetch.with_header("bad name", "1"),
+        Err(HeaderError::InvalidName(_))
+    ));
+    assert!(matches!(
+        fetch.with_bearer_auth("a\r\nb"),
+        Err(HeaderError::InvalidValue(_))
+    ));
+    assert!(matches!(
+        fetch.with_header("X-Emoji", "\u{1F600}"),
+        Err(HeaderError::InvalidValue(_))
+    ));
+    fetch.with_header("X-Name", "Zoë").unwrap();
+}
+
+#[test]
+fn form_url_encoded() {
+    let mut form = FormUrlEncoded::from_pairs([("name", "Jane Doe"), ("q", "a+b&c")]);
+    form.append("empty", "");
+    assert_eq!(form.as_str(), "name=Jane+Doe&q=a%2Bb%26c&empty=");
+    assert!(form
+        .content_type()
+        .unwrap()
+        .starts_with("application/x-www-form-urlencoded"));
+}
+
+#[test]
+fn mock_transport() {
+    let transport = MockTransport::new();
+    transport
+        .mock(
+            MockRequest::new(Method::Post, "https://example.com/echo")
+                .with_header("Authorization", "Bearer token")
+                .unwrap()
+                .with_body(&Text("ping".into()))
+                .unwrap(),
+            Response::new(201, HeaderMap::new(), "pong"),
+        )
+        .mock(
+            MockRequest::new(Method::Post, "https://example.com/echo"),
+            Response::new(401, HeaderMap::new(), ""),
+        );
+
+    let mut fetch = Fetch::post("echo");
+    fetch
+        .with_base_url("https://example.com")
+        .with_transport(transport.clone())
+        .with_body(&Text("ping".into()))
+        .unwrap();
+    assert_eq!(now(fetch.execute::<Response>()).unwrap().status(), 401);
+
+    fetch.with_bearer_auth("token").unwrap();
+    let response = now(fetch.execute::<Response>()).unwrap();
+    assert_eq!(response.status(), 201);
+    assert_eq!(now(response.text()).unwrap(), "pong");
+    assert_eq!(now(fetch.execute::<Text>()).unwrap(), Text("pong".into()));
+
+    let (handle, future) = fetch.execute_abortable::<Text>();
+    handle.abort();
+    assert!(matches!(now(future), Err(FetchError::Aborted)));
+
+    let error = now(Fetch::get("/missing")
+        .with_transport(transport.clone())
+        .execute::<Text>())
+    .unwrap_err();
+    assert_eq!(error.to_string(), "transport error: no mock matches GET /missing");
+    assert_eq!(transport.requests().len(), 4);
+}
+
+#[test]
+fn mock_retry() {
+    let transport = MockTransport::new();
+    transport.mock(
+        MockRequest::new(Method::Get, "https://example.com/flaky"),
+        Response::new(503, HeaderMap::new(), ""),
+    );
+
+    let clock = ManualClock::new();
+    let bounds = RetryPolicy::default().with_jitter(false);
+    let mut fetch = Fetch::get("https://example.com/flaky");
+    fetch
+        .with_transport(transport.clone())
+        .with_clock(clock.clone())
+        .with_retry(RetryPolicy::default())
+        .with_timeout(5000);
+
+    let mut cx = Context::from_waker(Waker::noop());
+    let mut future = pin!(fetch.execute::<Response>());
+    assert!(future.as_mut().poll(&mut cx).is_pending());
+    assert_eq!(transport.requests().len(), 1);
+    clock.advance(bounds.backoff(1));
+    assert!(future.as_mut().poll(&mut cx).is_pending());
+    assert_eq!(transport.requests().len(), 2);
+    clock.advance(bounds.backoff(2));
+    match future.as_mut().poll(&mut cx) {
+        Poll::Ready(response) => assert_eq!(response.unwrap().status(), 503),
+        Poll::Pending => panic!("request is still pending"),
+    }
+    assert_eq!(transport.requests().len(), 3);
+
+    // The second retry would happen after 750ms.
+    fetch.with_retry(bounds).with_timeout(600);
+    let mut future = pin!(fetch.execute::<Response>());
+    assert!(future.as_mut().poll(&mut cx).is_pending());
+    clock.advance(600);
+    assert!(matches!(
+        future.as_mut().poll(&mut cx),
+        Poll::Ready(Err(FetchError::TimedOut))
+    ));
+    assert_eq!(transport.requests().len(), 5);
+}
+
+#[test]
+fn client_interceptors() {
+    let transport = MockTransport::new();
+    transport
+        .mock(
+            MockRequest::new(Method::Get, "https://example.com/api/me")
+                .with_header("Authorization", "Bearer fresh")
+                .unwrap()
+                .with_header("X-Correlation-Id", "2")
+                .unwrap(),
+            Response::new(200, HeaderMap::new(), "jane"),
+        )
+        .mock(
+            MockRequest::new(Method::Get, "https://example.com/api/me"),
+            Response::new(401, HeaderMap::new(), ""),
+        );
+
+    let token = Rc::new(Cell::new("expired"));
+    let next_id = Rc::new(Cell::new(0));
+    let mut client = Client::new();
+    client
+        .with_base_url("https://example.com/api/")
+        .with_transport(transport.clone())
+        .with_header("Accept", "text/plain")
+        .unwrap()
+        .with_request_interceptor({
+            let token = token.clone();
+            move |request| {
+                request.with_bearer_auth(token.get()).unwrap();
+            }
+        })
+        .with_request_interceptor(move |request| {
+            next_id.set(next_id.get() + 1);
+            request
+                .with_header("X-Correlation-Id", &next_id.get().to_string())
+                .unwrap();
+        })
+        .with_response_interceptor(move |mut request, result| {
+            let token = token.clone();
+            async move {
+                match result {
+                    Ok(response) if response.status() == 401 => {
+                        token.set("fresh");
+                        request.with_bearer_auth(token.get()).unwrap();
+                        request.with_header("X-Correlation-Id", "2").unwrap();
+                        request.execute().await
+                    }
+                    result => result,
+                }
+            }
+        });
+
+    let request = client.get("me");
+    assert_eq!(request.headers().get("Accept"), Some("text/plain"));
+    assert_eq!(now(client.execute::<Text>(&request)).unwrap(), Text("jane".into()));
+    assert_eq!(transport.requests().len(), 2);
+}
+
+#[test]
+fn query_cache() {
+    let transport = MockTransport::new();
+    transport.mock(
+        MockRequest::new(Method::Get, "/users"),
+        Response::new(200, HeaderMap::new(), "jane"),
+    );
+    let clock = ManualClock::new();
+    let cache = QueryCache::with_clock(clock.clone(), 1000);
+    let mut request = Fetch::get("/users");
+    request.with_transport(transport.clone());
+
+    let query = cache.query::<Text>(&request);
+    assert!(query.get().fetching);
+    run_until_stalled();
+    let query = query.get();
+    assert_eq!(query.data, Some(Text("jane".into())));
+    assert!(query.error.is_none() && !query.fetching);
+
+    clock.advance(999);
+    cache.query::<Text>(&request);
+    run_until_stalled();
+    assert_eq!(transport.requests().len(), 1);
+
+    clock.advance(1);
+    cache.query::<Text>(&request);
+    run_until_stalled();
+    assert_eq!(transport.requests().len(), 2);
+
+    cache.invalidate(&QueryKey::from(&request));
+    cache.invalidate_where(|key| key.url.starts_with("/posts"));
+    run_until_stalled();
+    assert_eq!(transport.requests().len(), 3);
+
+    let missing = cache.query::<Text>(Fetch::get("/missing").with_transport(transport));
+    run_until_stalled();
+    let missing = missing.get();
+    assert!(missing.data.is_none() && missing.error.is_some());
+
+    let fetches = Rc::new(Cell::new(0));
+    let key = QueryKey::new(Method::Get, "/count".into());
+    let query = cache.query_with(key.clone(), {
+        let fetches = fetches.clone();
+        move || {
+            fetches.set(fetches.get() + 1);
+            future::ready(Ok::<_, FetchError>(fetches.get()))
+        }
+    });
+    // Invalidated while in flight, so fetched again once done.
+    cache.invalidate(&key);
+    run_until_stalled();
+    assert_eq!((query.get().data, fetches.get()), (Some(2), 2));
+
+    let _unsubscriber = query.for_each({
+        let (cache, key) = (cache.clone(), key.clone());
+        move |query| {
+            if query.data == Some(2) && !query.fetching {
+                cache.invalidate(&key);
+            }
+        }
+    });
+    run_until_stalled();
+    assert_eq!(query.get().data, Some(3));
+    assert!(!query.get().fetching);
+}
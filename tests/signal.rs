@@ -1,6 +1,15 @@
 //! Run these with [miri](https://github.com/rust-lang/miri).
 
-use wasmadeus::signal::SignalMut;
+use std::cell::{Cell, RefCell};
+use std::future::Future;
+use std::pin::pin;
+use std::rc::Rc;
+use std::task::{Context, Poll, Waker};
+
+use futures::StreamExt;
+use wasmadeus::signal::{
+    ManualClock, Scope, Signal, SignalGetError, SignalMapMut, SignalMut, SignalUpdatingError, SignalVecMut, Store,
+};
 
 #[test]
 fn unsubscribe_in_notify() {
@@ -41,3 +50,265 @@ fn map() {
     let double = half.map(|i| i * 2);
     assert_eq!(double.get(), 42);
 }
+
+#[test]
+fn debounce() {
+    let clock = ManualClock::new();
+    let signal = SignalMut::new(0);
+    let debounced = signal.debounce_with(clock.clone(), 100);
+
+    clock.advance(50);
+    signal.set(1);
+    clock.advance(50);
+    signal.set(2);
+    clock.advance(99);
+    assert_eq!(debounced.try_get(), Err(SignalGetError::Uninit));
+
+    clock.advance(1);
+    assert_eq!(debounced.get(), 2);
+}
+
+#[test]
+fn throttle() {
+    let clock = ManualClock::new();
+    let signal = SignalMut::new(0);
+    let throttled = signal.throttle_with(clock.clone(), 100);
+    assert_eq!(throttled.get(), 0);
+
+    signal.set(1);
+    clock.advance(50);
+    signal.set(2);
+    assert_eq!(throttled.get(), 0);
+
+    clock.advance(50);
+    assert_eq!(throttled.get(), 2);
+
+    clock.advance(100);
+    signal.set(3);
+    assert_eq!(throttled.get(), 3);
+}
+
+#[test]
+fn to_stream() {
+    let mut cx = Context::from_waker(Waker::noop());
+    let signal = SignalMut::new(1);
+    let mut stream = signal.to_stream();
+    assert_eq!(stream.poll_next_unpin(&mut cx), Poll::Ready(Some(1)));
+    assert_eq!(stream.poll_next_unpin(&mut cx), Poll::Pending);
+
+    signal.set(2);
+    signal.set(3);
+    assert_eq!(stream.poll_next_unpin(&mut cx), Poll::Ready(Some(3)));
+
+    drop(signal);
+    assert_eq!(stream.poll_next_unpin(&mut cx), Poll::Ready(None));
+}
+
+#[test]
+fn wait_for() {
+    let mut cx = Context::from_waker(Waker::noop());
+    let signal = SignalMut::new(1);
+    let mut future = pin!(signal.wait_for(|&n| n % 2 == 0));
+    assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+
+    signal.set(3);
+    assert_eq!(future.as_mut().poll(&mut cx), Poll::Pending);
+
+    signal.set(4);
+    signal.set(5);
+    assert_eq!(future.as_mut().poll(&mut cx), Poll::Ready(4));
+
+    let mut changed = pin!(signal.changed());
+    signal.set(6);
+    assert_eq!(changed.as_mut().poll(&mut cx), Poll::Ready(6));
+}
+
+#[test]
+fn vec_adapters() {
+    let vec = SignalVecMut::new(vec![5, 2, 8]);
+    let doubled = vec.map(|n| n * 2);
+    let even = vec.filter(|n| n % 2 == 0);
+    let sorted = vec.sort_by(|a, b| a.cmp(b));
+
+    vec.push(3);
+    vec.insert(1, 4);
+    vec.swap(0, 3);
+    vec.remove(2);
+    vec.swap(1, 3);
+
+    let values = vec.to_vec();
+    assert_eq!(values, [8, 3, 5, 4]);
+    assert_eq!(doubled.to_vec(), [16, 6, 10, 8]);
+    assert_eq!(even.to_vec(), [8, 4]);
+    assert_eq!(sorted.to_vec(), [3, 4, 5, 8]);
+
+    vec.clear();
+    assert!(doubled.is_empty() && even.is_empty() && sorted.is_empty());
+}
+
+#[test]
+fn map_key_signal() {
+    let map = SignalMapMut::default();
+    let alice = map.signal("alice");
+
+    let count = Rc::new(Cell::new(0));
+    let counter = count.clone();
+    alice.for_each_forever(move |_| counter.set(counter.get() + 1));
+    assert_eq!((alice.get(), count.get()), (None, 1));
+
+    map.insert("bob", 1);
+    map.clear();
+    assert_eq!((alice.get(), count.get()), (None, 1));
+
+    map.insert("alice", 2);
+    map.insert("bob", 3);
+    map.insert("alice", 4);
+    assert_eq!((alice.get(), count.get()), (Some(4), 3));
+
+    map.insert("alice", 4);
+    map.insert("bob", 5);
+    assert_eq!((alice.get(), count.get()), (Some(4), 3));
+
+    map.remove(&"alice");
+    assert_eq!((alice.get(), count.get()), (None, 4));
+}
+
+#[test]
+fn store_lens() {
+    #[derive(Clone, Default)]
+    struct State {
+        name: &'static str,
+        cart: (u32, u32),
+    }
+
+    let store = Store::new(State::default());
+    let name = store.lens(|state| &state.name, |state| &mut state.name);
+    let total = store
+        .lens(|state| &state.cart, |state| &mut state.cart)
+        .lens(|cart| &cart.1, |cart| &mut cart.1);
+
+    let count = Rc::new(Cell::new(0));
+    let counter = count.clone();
+    total.for_each_forever(move |_| counter.set(counter.get() + 1));
+
+    name.set("alice");
+    store.mutate(|state| state.cart.0 = 2);
+    assert_eq!(count.get(), 1);
+
+    total.set(42);
+    assert_eq!((total.get(), count.get()), (42, 2));
+    assert_eq!(name.get(), "alice");
+
+    let (first, second) = (name.clone(), total.clone());
+    store.for_each_forever(move |state| {
+        if state.name == "alice" {
+            first.set("bob");
+            second.set(7);
+        }
+    });
+    assert_eq!((name.get(), total.get()), ("bob", 7));
+}
+
+#[test]
+fn scope_release() {
+    let signal = SignalMut::new(0);
+    let count = Rc::new(Cell::new(0));
+
+    let scope = Scope::new();
+    let counter = count.clone();
+    scope.run(|| signal.for_each_forever(move |_| counter.set(counter.get() + 1)));
+    signal.set(1);
+    assert_eq!(count.get(), 2);
+
+    drop(scope);
+    signal.set(2);
+    assert_eq!(count.get(), 2);
+}
+
+#[test]
+fn computed_dependencies() {
+    let (flag, a, b) = (SignalMut::new(true), SignalMut::new(1), SignalMut::new(10));
+    let runs = Rc::new(Cell::new(0));
+
+    let sum = {
+        let (flag, a, b, runs) = (flag.clone(), a.clone(), b.clone(), runs.clone());
+        Signal::computed(move || {
+            runs.set(runs.get() + 1);
+            if flag.get() {
+                a.get() + a.get()
+            } else {
+                b.get()
+            }
+        })
+    };
+    assert_eq!((sum.get(), runs.get()), (2, 1));
+
+    a.set(2);
+    b.set(20);
+    assert_eq!((sum.get(), runs.get()), (4, 2));
+
+    flag.set(false);
+    a.set(3);
+    assert_eq!((sum.get(), runs.get()), (20, 3));
+
+    b.set(30);
+    assert_eq!((sum.get(), runs.get()), (30, 4));
+}
+
+#[test]
+fn set_in_notify() {
+    let signal = SignalMut::new(0);
+    let seen = Rc::new(RefCell::new(Vec::new()));
+
+    let (setter, log) = (signal.clone(), seen.clone());
+    signal.for_each_forever(move |&value| {
+        if value % 2 == 1 {
+            setter.set(value + 1);
+        }
+    });
+    signal.for_each_forever(move |&value| log.borrow_mut().push(value));
+
+    signal.set(1);
+    assert_eq!(*seen.borrow(), [0, 1, 2]);
+    assert_eq!(signal.get(), 2);
+}
+
+#[test]
+fn set_in_notify_loop() {
+    let signal = SignalMut::new(0);
+
+    let setter = signal.clone();
+    signal.for_each_forever(move |&value| {
+        if value > 0 {
+            setter.set(value + 1);
+        }
+    });
+
+    assert_eq!(signal.try_set(1), Err(SignalUpdatingError::InfiniteLoop));
+    assert_eq!(signal.get(), 101);
+}
+
+#[cfg(feature = "debug")]
+#[test]
+fn debug_graph() {
+    use wasmadeus::signal::debug;
+
+    let price = SignalMut::new(10).named("price");
+    let total = price.map(|price| price * 3).named("cart \"total\"");
+    let mut unsub = total.for_each(|_| ());
+
+    let signals = debug::signals();
+    let price = signals.iter().find(|s| s.name.as_deref() == Some("price")).unwrap();
+    let total = signals.iter().find(|s| s.parents == [price.id]).unwrap();
+    assert_eq!((price.subscribers, total.subscribers), (1, 1));
+    assert_eq!(total.type_name, "i32");
+
+    assert!(debug::to_dot().contains(&format!("{} -> {};", price.id, total.id)));
+    assert!(debug::to_json().contains(r#""name":"cart \"total\"""#));
+
+    unsub.unsubscribe();
+    assert_eq!(
+        debug::signals().iter().find(|s| s.id == total.id).unwrap().subscribers,
+        0
+    );
+}
@@ -0,0 +1,19 @@
+use wasmadeus::signal::SignalMut;
+use wasmadeus::view::ErrorBoundary;
+
+#[test]
+fn boundary_catch() {
+    let boundary = ErrorBoundary::new();
+    let result = SignalMut::new(Ok(1));
+    let value = boundary.catch(&result);
+    assert_eq!(value.get(), 1);
+
+    result.set(Err("oops"));
+    assert_eq!(value.get(), 1);
+    assert_eq!(boundary.error().get().as_deref(), Some(&"oops"));
+
+    boundary.reset();
+    result.set(Ok(2));
+    assert_eq!(value.get(), 2);
+    assert_eq!(boundary.error().get(), None);
+}